@@ -2,9 +2,23 @@ pub mod other_world;
 pub mod other_query;
 pub mod other_query_state;
 pub mod other_query_iter;
+pub mod other_query_par_iter;
+pub mod other_query_set;
 pub mod other_res;
 pub mod other_res_mut;
+pub mod other_non_send;
+pub mod other_non_send_mut;
 pub mod other;
-//pub mod other_commands;
-//pub mod other_world_query;
+pub mod other_world_query;
+pub mod other_commands;
+pub mod other_schedule;
+pub mod other_events;
+pub mod other_entity;
+pub mod other_snapshot;
+pub mod other_rollback;
+pub mod other_scene;
+pub mod other_worlds;
+pub mod per_entity_other_query;
+pub mod nested;
+mod other_ticks;
 //pub mod other_query_state;
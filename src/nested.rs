@@ -0,0 +1,191 @@
+use crate::other::Other;
+use crate::other_world::maintain_world;
+use bevy::app::AppBuilder;
+use bevy::app::CoreStage;
+use bevy::ecs::archetype::Archetype;
+use bevy::ecs::component::Component;
+use bevy::ecs::component::ComponentId;
+use bevy::ecs::system::SystemState;
+use bevy::ecs::system::IntoExclusiveSystem;
+use bevy::ecs::world::World;
+use bevy::utils::HashMap;
+use core::any::Any;
+use core::any::TypeId;
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ops::DerefMut;
+
+/// The subworld `W2`, stored as a resource inside the subworld `W1`.
+///
+/// Can be used anywhere a subworld type is expected, e.g. `OtherQuery<Nested<W1, W2>, &T>` runs
+/// the query inside `W2`. `W1` can itself be a `Nested`, so chains of any depth work. Every link
+/// of the chain has to be registered with [`NestedSubWorldAppExt::add_nested_subworld`].
+#[repr(transparent)]
+pub struct Nested<W1: DerefMut<Target = World> + Component, W2: DerefMut<Target = World> + Component>{
+    world: W2,
+    w: PhantomData<fn() -> W1>,
+}
+
+impl<W1: DerefMut<Target = World> + Component, W2: DerefMut<Target = World> + Component> Deref for Nested<W1, W2>{
+    type Target = World;
+
+    fn deref(&self) -> &<Self as std::ops::Deref>::Target {
+        &self.world
+    }
+}
+
+impl<W1: DerefMut<Target = World> + Component, W2: DerefMut<Target = World> + Component> DerefMut for Nested<W1, W2>{
+    fn deref_mut(&mut self) -> &mut <Self as std::ops::Deref>::Target {
+        &mut self.world
+    }
+}
+
+struct NestedLocator<W>{
+    get: fn(&World) -> Option<&W>,
+    get_mut: fn(&mut World) -> Option<&mut W>,
+    get_unchecked_mut: unsafe fn(&World) -> Option<&mut W>,
+    reads: fn(&mut World) -> Vec<ComponentId>,
+}
+
+/// Knows how to reach every registered [`Nested`] subworld from the app world.
+#[derive(Default)]
+pub struct NestedSubWorlds{
+    locators: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl NestedSubWorlds{
+    fn get<W: DerefMut<Target = World> + Component>(&self) -> Option<&NestedLocator<W>>{
+        self.locators
+            .get(&TypeId::of::<W>())
+            .and_then(|locator| locator.downcast_ref::<NestedLocator<W>>())
+    }
+}
+
+/// Fetches the subworld `W` from `world`, either as a resource or by walking the chain of a
+/// registered [`Nested`] subworld.
+pub fn get_subworld<W: DerefMut<Target = World> + Component>(world: &World) -> Option<&W>{
+    if let Some(other) = world.get_resource::<W>(){
+        return Some(other);
+    }
+    let get = world.get_resource::<NestedSubWorlds>()?.get::<W>()?.get;
+    get(world)
+}
+
+/// Mutable version of [`get_subworld`].
+pub fn get_subworld_mut<W: DerefMut<Target = World> + Component>(world: &mut World) -> Option<&mut W>{
+    if world.contains_resource::<W>(){
+        return world.get_resource_mut::<W>().map(|other| other.into_inner());
+    }
+    let get_mut = world.get_resource::<NestedSubWorlds>()?.get::<W>()?.get_mut;
+    get_mut(world)
+}
+
+/// Mutable version of [`get_subworld`] that only needs shared access to `world`.
+///
+/// # Safety
+/// The caller has to make sure nothing else accesses the subworld `W` for as long as the returned
+/// reference is alive.
+pub(crate) unsafe fn get_subworld_unchecked_mut<W: DerefMut<Target = World> + Component>(world: &World) -> Option<&mut W>{
+    if let Some(other) = world.get_resource_unchecked_mut::<W>(){
+        return Some(other.into_inner());
+    }
+    let get_unchecked_mut = world.get_resource::<NestedSubWorlds>()?.get::<W>()?.get_unchecked_mut;
+    get_unchecked_mut(world)
+}
+
+/// The outer world resources that reaching the subworld `W` reads, i.e. the marker and the
+/// enclosing subworld of every link of its chain. Empty if `W` is not a [`Nested`] subworld.
+pub(crate) fn nested_reads<W: DerefMut<Target = World> + Component>(world: &mut World) -> Vec<ComponentId>{
+    let reads = world
+        .get_resource::<NestedSubWorlds>()
+        .and_then(|nested| nested.get::<W>())
+        .map(|locator| locator.reads);
+    match reads{
+        Some(reads) => reads(world),
+        None => Vec::new(),
+    }
+}
+
+/// The resources of the outer world the subworld `W` is reached through, ending with `W` itself.
+pub(crate) fn subworld_resource_ids<W: DerefMut<Target = World> + Component>(world: &mut World) -> Vec<ComponentId>{
+    let mut resource_ids = nested_reads::<W>(world);
+    resource_ids.push(world.initialize_resource::<W>());
+    resource_ids
+}
+
+/// Adds the archetype level access of a subworld param to `system_state`, which is what the
+/// executor uses to decide which systems can run in parallel. Resources only show up in the
+/// resource archetype, components in every archetype that has them.
+pub(crate) fn add_resource_archetype_access(reads: &[ComponentId], writes: &[ComponentId], archetype: &Archetype, system_state: &mut SystemState){
+    for component_id in reads{
+        if let Some(archetype_component_id) = archetype.get_archetype_component_id(*component_id){
+            system_state.archetype_component_access.add_read(archetype_component_id);
+        }
+    }
+    for component_id in writes{
+        if let Some(archetype_component_id) = archetype.get_archetype_component_id(*component_id){
+            system_state.archetype_component_access.add_write(archetype_component_id);
+        }
+    }
+}
+
+fn get_nested<W1: DerefMut<Target = World> + Component, W2: DerefMut<Target = World> + Component>(world: &World) -> Option<&Nested<W1, W2>>{
+    let inner = get_subworld::<W1>(world)?.get_resource::<W2>()?;
+    // SAFE: Nested is a transparent wrapper around W2
+    Some(unsafe{ &*(inner as *const W2 as *const Nested<W1, W2>) })
+}
+
+fn get_nested_mut<W1: DerefMut<Target = World> + Component, W2: DerefMut<Target = World> + Component>(world: &mut World) -> Option<&mut Nested<W1, W2>>{
+    let inner = get_subworld_mut::<W1>(world)?.get_resource_mut::<W2>()?.into_inner();
+    // SAFE: Nested is a transparent wrapper around W2
+    Some(unsafe{ &mut *(inner as *mut W2 as *mut Nested<W1, W2>) })
+}
+
+unsafe fn get_nested_unchecked_mut<W1: DerefMut<Target = World> + Component, W2: DerefMut<Target = World> + Component>(world: &World) -> Option<&mut Nested<W1, W2>>{
+    // NOTE: W1 is only borrowed immutably, only the resource holding W2 is borrowed mutably
+    let inner = get_subworld::<W1>(world)?.get_resource_unchecked_mut::<W2>()?.into_inner();
+    // SAFE: Nested is a transparent wrapper around W2
+    Some(&mut *(inner as *mut W2 as *mut Nested<W1, W2>))
+}
+
+fn nested_chain_reads<W1: DerefMut<Target = World> + Component, W2: DerefMut<Target = World> + Component>(world: &mut World) -> Vec<ComponentId>{
+    // NOTE: this is the marker OtherRes<W1, W2> reads and OtherResMut<W1, W2> writes, so
+    // borrowing W2 mutably from W1 conflicts with anything running inside of it. W1 itself is read
+    // as well, so a ResMut<W1> conflicts with it too
+    let mut reads = vec![world.initialize_resource::<Other<W1, W2>>(), world.initialize_resource::<W1>()];
+    reads.extend(nested_reads::<W1>(world));
+    reads
+}
+
+/// Makes [`Nested<W1, W2>`] reachable from the app world.
+pub fn register_nested_subworld<W1: DerefMut<Target = World> + Component, W2: DerefMut<Target = World> + Component>(world: &mut World){
+    let locator = NestedLocator::<Nested<W1, W2>>{
+        get: get_nested::<W1, W2>,
+        get_mut: get_nested_mut::<W1, W2>,
+        get_unchecked_mut: get_nested_unchecked_mut::<W1, W2>,
+        reads: nested_chain_reads::<W1, W2>,
+    };
+    world
+        .get_resource_or_insert_with(NestedSubWorlds::default)
+        .locators
+        .insert(TypeId::of::<Nested<W1, W2>>(), Box::new(locator));
+}
+
+pub trait NestedSubWorldAppExt{
+    /// Registers the subworld `W2` stored inside `W1` as [`Nested<W1, W2>`] and maintains it
+    /// every frame, like [`SubWorldPlugin`](crate::other_world::SubWorldPlugin) does for top level
+    /// subworlds. `W1` has to be registered first if it is nested itself.
+    fn add_nested_subworld<W1: DerefMut<Target = World> + Component, W2: DerefMut<Target = World> + Component>(&mut self) -> &mut Self;
+}
+
+impl NestedSubWorldAppExt for AppBuilder{
+    fn add_nested_subworld<W1: DerefMut<Target = World> + Component, W2: DerefMut<Target = World> + Component>(&mut self) -> &mut Self{
+        register_nested_subworld::<W1, W2>(self.world_mut());
+        let mut last_check_tick = 0;
+        self.add_system_to_stage(CoreStage::Last, (move |world: &mut World| {
+            if let Some(other) = get_subworld_mut::<Nested<W1, W2>>(world){
+                maintain_world(other, &mut last_check_tick);
+            }
+        }).exclusive_system())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,121 @@
+use core::ops::Deref;
+use crate::nested::get_subworld;
+use crate::nested::get_subworld_mut;
+use crate::nested::nested_reads;
+use crate::nested::subworld_resource_ids;
+use crate::nested::add_resource_archetype_access;
+use crate::other::Other;
+use crate::other::SubWorldMarker;
+use bevy::ecs::archetype::Archetype;
+use bevy::ecs::component::ComponentId;
+use bevy::ecs::entity::Entities;
+use bevy::ecs::system::Commands;
+use bevy::ecs::system::CommandQueue;
+use bevy::ecs::system::SystemParamFetch;
+use bevy::ecs::system::SystemParamState;
+use bevy::ecs::system::SystemState;
+use core::marker::PhantomData;
+use bevy::ecs::system::SystemParam;
+use bevy::ecs::world::World;
+use core::ops::DerefMut;
+use bevy::ecs::component::Component;
+
+/// A list of commands that will be run to modify the subworld `W`.
+///
+/// Works just like [`Commands`], but entities are reserved in and commands are applied to the
+/// inner world at the end of the stage instead of the app world.
+pub struct OtherCommands<'a, W: DerefMut<Target = World> + Component> {
+    commands: Commands<'a>,
+    w: PhantomData<W>,
+}
+
+impl<'a, W: DerefMut<Target = World> + Component> SystemParam for OtherCommands<'a, W> {
+    type Fetch = OtherCommandsState<W>;
+}
+
+pub struct OtherCommandsState<W: DerefMut<Target = World> + Component> {
+    queue: CommandQueue,
+    // NOTE: the subworld resources and the Other<W, Entities> marker, read at the archetype level
+    // as well
+    reads: Vec<ComponentId>,
+    marker: PhantomData<W>,
+}
+
+// SAFE: only local state is accessed, entities are reserved through the atomic allocator of the
+// subworld and every structural change is deferred to apply, which has exclusive world access.
+unsafe impl<W: DerefMut<Target = World> + Component> SystemParamState for OtherCommandsState<W> {
+    type Config = ();
+
+    fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
+        let outer_component_id = world.initialize_resource::<Other<W, Entities>>();
+        let world_id = world.initialize_resource::<W>();
+        let mut nested_reads = nested_reads::<W>(world);
+        nested_reads.push(world.initialize_resource::<Other<W, SubWorldMarker>>());
+        let mut reads = subworld_resource_ids::<W>(world);
+        reads.push(outer_component_id);
+        let combined_access = system_state.component_access_set.combined_access_mut();
+        if combined_access.has_write(world_id){
+            panic!(
+                "OtherCommands<{}> in system {} conflicts with a ResMut<{0}> access. Allowing this would break Rust's mutability rules. Consider removing the duplicate access.",
+                std::any::type_name::<W>(), system_state.name);
+        }
+        combined_access.add_read(outer_component_id);
+        for component_id in nested_reads{
+            if combined_access.has_write(component_id){
+                panic!(
+                    "OtherCommands<{}> in system {} conflicts with mutable access to it or a world it is nested in. Allowing this would break Rust's mutability rules. Consider removing the duplicate access.",
+                    std::any::type_name::<W>(), system_state.name);
+            }
+            combined_access.add_read(component_id);
+        }
+
+        Self {
+            queue: CommandQueue::default(),
+            reads,
+            marker: PhantomData,
+        }
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+        add_resource_archetype_access(&self.reads, &[], archetype, system_state);
+    }
+
+    fn apply(&mut self, world: &mut World) {
+        let world = get_subworld_mut::<W>(world).expect("Couldn't find world!");
+        self.queue.apply(world);
+    }
+
+    fn default_config() {}
+}
+
+impl<'a, W: DerefMut<Target = World> + Component> SystemParamFetch<'a> for OtherCommandsState<W> {
+    type Item = OtherCommands<'a, W>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'a mut Self,
+        _system_state: &'a SystemState,
+        world: &'a World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        let world = get_subworld::<W>(world).expect("Couldn't find world!");
+        OtherCommands {
+            commands: Commands::new(&mut state.queue, world),
+            w: PhantomData,
+        }
+    }
+}
+
+impl<'a, W: DerefMut<Target = World> + Component> Deref for OtherCommands<'a, W> {
+    type Target = Commands<'a>;
+
+    fn deref(&self) -> &<Self as std::ops::Deref>::Target {
+        &self.commands
+    }
+}
+
+impl<'a, W: DerefMut<Target = World> + Component> DerefMut for OtherCommands<'a, W> {
+    fn deref_mut(&mut self) -> &mut <Self as std::ops::Deref>::Target {
+        &mut self.commands
+    }
+}
\ No newline at end of file
@@ -0,0 +1,264 @@
+use bevy::ecs::entity::Entity;
+use bevy::ecs::entity::EntityMap;
+use bevy::ecs::entity::MapEntitiesError;
+use bevy::ecs::reflect::ReflectComponent;
+use bevy::ecs::reflect::ReflectMapEntities;
+use bevy::ecs::system::Command;
+use bevy::ecs::system::Commands;
+use bevy::ecs::world::World;
+use bevy::ecs::component::Component;
+use bevy::log::warn;
+use bevy::reflect::TypeRegistry;
+use bevy::reflect::TypeRegistryArc;
+use bevy::utils::HashSet;
+use crate::nested::get_subworld_mut;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::DerefMut;
+
+/// Remembers where entities moved between the app world and the subworld `W` ended up.
+///
+/// Entries are kept after the new entity is despawned or moved again, so they can go stale.
+pub struct OtherEntityMap<W: DerefMut<Target = World> + Component>{
+    to_subworld: EntityMap,
+    from_subworld: EntityMap,
+    w: PhantomData<W>,
+}
+
+impl<W: DerefMut<Target = World> + Component> Default for OtherEntityMap<W>{
+    fn default() -> Self{
+        Self{
+            to_subworld: EntityMap::default(),
+            from_subworld: EntityMap::default(),
+            w: PhantomData,
+        }
+    }
+}
+
+impl<W: DerefMut<Target = World> + Component> OtherEntityMap<W>{
+    /// The entity in the subworld that the app world `entity` was transferred to.
+    pub fn subworld_entity(&self, entity: Entity) -> Option<Entity>{
+        self.to_subworld.get(entity).ok()
+    }
+
+    /// The entity in the app world that the subworld `entity` was retrieved to.
+    pub fn outer_entity(&self, entity: Entity) -> Option<Entity>{
+        self.from_subworld.get(entity).ok()
+    }
+}
+
+/// Why entities couldn't be moved between the app world and a subworld.
+#[derive(Debug)]
+pub enum MoveEntitiesError{
+    /// The subworld doesn't exist, or a world it is nested in doesn't. Nothing was moved.
+    NoSuchWorld,
+    /// The entity doesn't exist in the world it was moved from. Nothing was moved.
+    NoSuchEntity(Entity),
+    /// The entity was passed more than once. Nothing was moved.
+    DuplicateEntity(Entity),
+    /// The entity has components that don't reflect [`Component`](ReflectComponent) and would be
+    /// lost by the move. Nothing was moved.
+    Uncloneable(Entity, Vec<String>),
+    /// The entities were moved, but a component references an entity that wasn't moved along.
+    /// References of that component type may still point into the world the entities came from.
+    /// The new entities can be looked up in [`OtherEntityMap`].
+    UnmappedEntity(MapEntitiesError),
+}
+
+impl fmt::Display for MoveEntitiesError{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        match self{
+            MoveEntitiesError::NoSuchWorld => write!(f, "the subworld does not exist"),
+            MoveEntitiesError::NoSuchEntity(entity) => write!(f, "entity {:?} does not exist", entity),
+            MoveEntitiesError::DuplicateEntity(entity) => write!(f, "entity {:?} was passed more than once", entity),
+            MoveEntitiesError::Uncloneable(entity, components) => write!(f, "entity {:?} has components that can't be moved between worlds: {}", entity, components.join(", ")),
+            MoveEntitiesError::UnmappedEntity(error) => write!(f, "couldn't map an entity reference of a moved entity: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for MoveEntitiesError{}
+
+/// Moves `entity` and all of its components from the app world into the subworld `W`, returning
+/// the new entity in the subworld.
+///
+/// Components are copied through the [`ReflectComponent`] registered in the app's
+/// [`TypeRegistryArc`]. If the subworld or the entity doesn't exist, or the entity has a component
+/// without one, nothing is moved.
+pub fn transfer_entity<W: DerefMut<Target = World> + Component>(world: &mut World, entity: Entity) -> Result<Entity, MoveEntitiesError>{
+    transfer_entities::<W>(world, &[entity]).map(|entities| entities[0])
+}
+
+/// Like [`transfer_entity`], but moves several entities at once. References between the moved
+/// entities are mapped to the new entities, as long as the component registers [`ReflectMapEntities`].
+pub fn transfer_entities<W: DerefMut<Target = World> + Component>(world: &mut World, entities: &[Entity]) -> Result<Vec<Entity>, MoveEntitiesError>{
+    let registry = type_registry(world);
+    let registry = registry.read();
+    let (entity_map, result) = subworld_scope::<W, _>(world, |world, other| {
+        move_entities(world, other, entities, &registry)
+    })??;
+    let mut map = world.get_resource_or_insert_with(OtherEntityMap::<W>::default);
+    let entities = record(&mut map.to_subworld, &entity_map, entities);
+    result.map(|_| entities)
+}
+
+/// Moves `entity` and all of its components from the subworld `W` back into the app world,
+/// returning the new entity in the app world.
+///
+/// See [`transfer_entity`] for how components are copied.
+pub fn retrieve_entity<W: DerefMut<Target = World> + Component>(world: &mut World, entity: Entity) -> Result<Entity, MoveEntitiesError>{
+    retrieve_entities::<W>(world, &[entity]).map(|entities| entities[0])
+}
+
+/// Like [`retrieve_entity`], but moves several entities at once, mapping references between them.
+pub fn retrieve_entities<W: DerefMut<Target = World> + Component>(world: &mut World, entities: &[Entity]) -> Result<Vec<Entity>, MoveEntitiesError>{
+    let registry = type_registry(world);
+    let registry = registry.read();
+    let (entity_map, result) = subworld_scope::<W, _>(world, |world, other| {
+        move_entities(other, world, entities, &registry)
+    })??;
+    let mut map = world.get_resource_or_insert_with(OtherEntityMap::<W>::default);
+    let entities = record(&mut map.from_subworld, &entity_map, entities);
+    result.map(|_| entities)
+}
+
+fn type_registry(world: &World) -> TypeRegistryArc{
+    world
+        .get_resource::<TypeRegistryArc>()
+        .expect("Moving entities between worlds needs a TypeRegistryArc resource")
+        .clone()
+}
+
+/// Runs `f` with the app world and the world of the subworld `W`. The subworld's world is taken out
+/// of the app world in the meantime, which works wherever the subworld is nested.
+fn subworld_scope<W: DerefMut<Target = World> + Component, R>(world: &mut World, f: impl FnOnce(&mut World, &mut World) -> R) -> Result<R, MoveEntitiesError>{
+    let mut other = match get_subworld_mut::<W>(world){
+        Some(other) => std::mem::take(&mut **other),
+        None => return Err(MoveEntitiesError::NoSuchWorld),
+    };
+    let result = f(world, &mut other);
+    **get_subworld_mut::<W>(world).expect("Couldn't find world!") = other;
+    Ok(result)
+}
+
+fn record(map: &mut EntityMap, entity_map: &EntityMap, entities: &[Entity]) -> Vec<Entity>{
+    entities
+        .iter()
+        .map(|&entity| {
+            let new_entity = entity_map.get(entity).unwrap();
+            map.insert(entity, new_entity);
+            new_entity
+        })
+        .collect()
+}
+
+/// Moves `entities` from `source` to `destination`. Fails without moving anything if an entity
+/// is missing, passed twice or would lose components. Otherwise, the returned map is complete, and
+/// the result holds the first entity reference that couldn't be mapped, if any.
+fn move_entities(source: &mut World, destination: &mut World, entities: &[Entity], registry: &TypeRegistry) -> Result<(EntityMap, Result<(), MoveEntitiesError>), MoveEntitiesError>{
+    let mut moves = Vec::with_capacity(entities.len());
+    let mut seen = HashSet::default();
+    for &entity in entities{
+        if !seen.insert(entity){
+            return Err(MoveEntitiesError::DuplicateEntity(entity));
+        }
+        let location = source
+            .entities()
+            .get(entity)
+            .ok_or(MoveEntitiesError::NoSuchEntity(entity))?;
+        let mut reflect_components = Vec::new();
+        let mut uncloneable = Vec::new();
+        for component_id in source.archetypes()[location.archetype_id].components(){
+            let info = source.components().get_info(component_id).unwrap();
+            match info
+                .type_id()
+                .and_then(|type_id| registry.get(type_id))
+                .and_then(|registration| registration.data::<ReflectComponent>()){
+                Some(reflect_component) => reflect_components.push(reflect_component),
+                None => uncloneable.push(info.name().to_string()),
+            }
+        }
+        if !uncloneable.is_empty(){
+            return Err(MoveEntitiesError::Uncloneable(entity, uncloneable));
+        }
+        moves.push((entity, reflect_components));
+    }
+
+    let mut entity_map = EntityMap::default();
+    for (entity, reflect_components) in moves{
+        let destination_entity = destination.spawn().id();
+        for reflect_component in reflect_components{
+            reflect_component.copy_component(source, destination, entity, destination_entity);
+        }
+        source.despawn(entity);
+        entity_map.insert(entity, destination_entity);
+    }
+    let mut result = Ok(());
+    for registration in registry.iter(){
+        if let Some(map_entities) = registration.data::<ReflectMapEntities>(){
+            // NOTE: mapping stops at the first reference to an entity that wasn't moved along, so
+            // every other component type is still mapped before the error is reported
+            if let Err(error) = map_entities.map_entities(destination, &entity_map){
+                if result.is_ok(){
+                    result = Err(MoveEntitiesError::UnmappedEntity(error));
+                }
+            }
+        }
+    }
+    Ok((entity_map, result))
+}
+
+/// [`Command`] form of [`transfer_entity`].
+pub struct TransferEntity<W: DerefMut<Target = World> + Component>{
+    pub entity: Entity,
+    w: PhantomData<W>,
+}
+
+impl<W: DerefMut<Target = World> + Component> Command for TransferEntity<W>{
+    fn write(self: Box<Self>, world: &mut World){
+        if let Err(error) = transfer_entity::<W>(world, self.entity){
+            warn!("Couldn't transfer {:?} into {}: {}", self.entity, std::any::type_name::<W>(), error);
+        }
+    }
+}
+
+/// [`Command`] form of [`retrieve_entity`].
+pub struct RetrieveEntity<W: DerefMut<Target = World> + Component>{
+    pub entity: Entity,
+    w: PhantomData<W>,
+}
+
+impl<W: DerefMut<Target = World> + Component> Command for RetrieveEntity<W>{
+    fn write(self: Box<Self>, world: &mut World){
+        if let Err(error) = retrieve_entity::<W>(world, self.entity){
+            warn!("Couldn't retrieve {:?} from {}: {}", self.entity, std::any::type_name::<W>(), error);
+        }
+    }
+}
+
+pub trait OtherEntityCommandsExt{
+    /// Transfers `entity` into the subworld `W` once the commands are applied. The new entity
+    /// can be looked up in [`OtherEntityMap<W>`] afterwards.
+    fn transfer_entity<W: DerefMut<Target = World> + Component>(&mut self, entity: Entity) -> &mut Self;
+    /// Retrieves `entity` from the subworld `W` once the commands are applied. The new entity
+    /// can be looked up in [`OtherEntityMap<W>`] afterwards.
+    fn retrieve_entity<W: DerefMut<Target = World> + Component>(&mut self, entity: Entity) -> &mut Self;
+}
+
+impl<'a> OtherEntityCommandsExt for Commands<'a>{
+    fn transfer_entity<W: DerefMut<Target = World> + Component>(&mut self, entity: Entity) -> &mut Self{
+        self.add(TransferEntity::<W>{
+            entity,
+            w: PhantomData,
+        });
+        self
+    }
+
+    fn retrieve_entity<W: DerefMut<Target = World> + Component>(&mut self, entity: Entity) -> &mut Self{
+        self.add(RetrieveEntity::<W>{
+            entity,
+            w: PhantomData,
+        });
+        self
+    }
+}
\ No newline at end of file
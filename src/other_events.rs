@@ -0,0 +1,176 @@
+use crate::other_res::OtherRes;
+use crate::other_res::OtherResState;
+use crate::other_res_mut::OtherResMut;
+use crate::other_res_mut::OtherResMutState;
+use crate::nested::get_subworld_mut;
+use bevy::app::AppBuilder;
+use bevy::app::CoreStage;
+use bevy::app::EventId;
+use bevy::app::Events;
+use bevy::app::ManualEventReader;
+use bevy::ecs::archetype::Archetype;
+use bevy::ecs::system::IntoExclusiveSystem;
+use bevy::ecs::system::SystemParamFetch;
+use bevy::ecs::system::SystemParamState;
+use bevy::ecs::system::SystemState;
+use bevy::ecs::system::SystemParam;
+use bevy::ecs::world::World;
+use core::ops::DerefMut;
+use bevy::ecs::component::Component;
+
+/// Reads events of type `T` stored in the subworld `W` in order and tracks which events have
+/// already been read.
+///
+/// Every system keeps its own cursor, so readers only need shared access to the subworld's
+/// [`Events<T>`] and can run in parallel.
+pub struct OtherEventReader<'a, W: DerefMut<Target = World> + Component, T: Component> {
+    reader: &'a mut ManualEventReader<T>,
+    events: OtherRes<'a, W, Events<T>>,
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: Component> OtherEventReader<'a, W, T> {
+    /// Iterates over the events this OtherEventReader has not seen yet. This updates the
+    /// OtherEventReader's event counter, which means subsequent event reads will not include events
+    /// that happened before now.
+    pub fn iter(&mut self) -> impl DoubleEndedIterator<Item = &T> {
+        self.reader.iter(&self.events)
+    }
+
+    /// Like [`iter`](Self::iter), except also returning the [`EventId`] of the events.
+    pub fn iter_with_id(&mut self) -> impl DoubleEndedIterator<Item = (&T, EventId<T>)> {
+        self.reader.iter_with_id(&self.events)
+    }
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParam for OtherEventReader<'a, W, T> {
+    type Fetch = OtherEventReaderState<W, T>;
+}
+
+pub struct OtherEventReaderState<W: DerefMut<Target = World> + Component, T: Component> {
+    reader: ManualEventReader<T>,
+    events: OtherResState<W, Events<T>>,
+}
+
+// SAFE: the reader is local state and the access of the inner OtherResState is applied to SystemState.
+unsafe impl<W: DerefMut<Target = World> + Component, T: Component> SystemParamState for OtherEventReaderState<W, T> {
+    type Config = ();
+
+    fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
+        Self {
+            reader: ManualEventReader::default(),
+            events: OtherResState::init(world, system_state, ()),
+        }
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+        self.events.new_archetype(archetype, system_state);
+    }
+
+    fn default_config() {}
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParamFetch<'a> for OtherEventReaderState<W, T> {
+    type Item = OtherEventReader<'a, W, T>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'a mut Self,
+        system_state: &'a SystemState,
+        world: &'a World,
+        change_tick: u32,
+    ) -> Self::Item {
+        OtherEventReader {
+            reader: &mut state.reader,
+            events: OtherResState::get_param(&mut state.events, system_state, world, change_tick),
+        }
+    }
+}
+
+/// Sends events of type `T` into the subworld `W`.
+pub struct OtherEventWriter<'a, W: DerefMut<Target = World> + Component, T: Component> {
+    events: OtherResMut<'a, W, Events<T>>,
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: Component> OtherEventWriter<'a, W, T> {
+    pub fn send(&mut self, event: T) {
+        self.events.send(event);
+    }
+
+    pub fn send_batch(&mut self, events: impl Iterator<Item = T>) {
+        self.events.extend(events);
+    }
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParam for OtherEventWriter<'a, W, T> {
+    type Fetch = OtherEventWriterState<W, T>;
+}
+
+pub struct OtherEventWriterState<W: DerefMut<Target = World> + Component, T: Component> {
+    events: OtherResMutState<W, Events<T>>,
+}
+
+// SAFE: the access of the inner OtherResMutState is applied to SystemState.
+unsafe impl<W: DerefMut<Target = World> + Component, T: Component> SystemParamState for OtherEventWriterState<W, T> {
+    type Config = ();
+
+    fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
+        Self {
+            events: OtherResMutState::init(world, system_state, ()),
+        }
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+        self.events.new_archetype(archetype, system_state);
+    }
+
+    fn default_config() {}
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParamFetch<'a> for OtherEventWriterState<W, T> {
+    type Item = OtherEventWriter<'a, W, T>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'a mut Self,
+        system_state: &'a SystemState,
+        world: &'a World,
+        change_tick: u32,
+    ) -> Self::Item {
+        OtherEventWriter {
+            events: OtherResMutState::get_param(&mut state.events, system_state, world, change_tick),
+        }
+    }
+}
+
+/// Swaps the event buffers of the [`Events<T>`] stored in the subworld `W`, inserting them if they
+/// do not exist yet. Does nothing while the subworld doesn't exist.
+///
+/// Subworlds that run their own [`OtherSchedule`](crate::other_schedule::OtherSchedule) can add
+/// [`Events::update_system`] to it instead.
+pub fn other_events_update_system<W: DerefMut<Target = World> + Component, T: Component>(world: &mut World) {
+    let other = match get_subworld_mut::<W>(world) {
+        Some(other) => other,
+        None => return,
+    };
+    match other.get_resource_mut::<Events<T>>() {
+        Some(mut events) => events.update(),
+        None => other.insert_resource(Events::<T>::default()),
+    }
+}
+
+pub trait OtherEventsAppExt {
+    /// Sets up the subworld `W` to handle events of type `T`, inserting its [`Events<T>`] and
+    /// updating them once per frame in [`CoreStage::First`].
+    fn add_other_event<W: DerefMut<Target = World> + Component, T: Component>(&mut self) -> &mut Self;
+}
+
+impl OtherEventsAppExt for AppBuilder {
+    fn add_other_event<W: DerefMut<Target = World> + Component, T: Component>(&mut self) -> &mut Self {
+        if let Some(world) = get_subworld_mut::<W>(self.world_mut()) {
+            if !world.contains_resource::<Events<T>>() {
+                world.insert_resource(Events::<T>::default());
+            }
+        }
+        self.add_system_to_stage(CoreStage::First, other_events_update_system::<W, T>.exclusive_system())
+    }
+}
\ No newline at end of file
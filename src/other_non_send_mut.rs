@@ -0,0 +1,157 @@
+use core::ops::Deref;
+use crate::nested::get_subworld;
+use crate::nested::get_subworld_mut;
+use crate::nested::nested_reads;
+use crate::nested::subworld_resource_ids;
+use crate::nested::add_resource_archetype_access;
+use crate::other::Other;
+use crate::other::NonSendMarker;
+use crate::other_ticks::SubWorldTicks;
+use bevy::ecs::archetype::Archetype;
+use bevy::ecs::component::ComponentId;
+use bevy::ecs::system::SystemParamFetch;
+use bevy::ecs::system::SystemParamState;
+use bevy::ecs::system::SystemState;
+use core::marker::PhantomData;
+use bevy::ecs::system::SystemParam;
+use bevy::ecs::world::World;
+use core::ops::DerefMut;
+use bevy::ecs::component::Component;
+use bevy::ecs::component::ComponentTicks;
+
+/// Unique borrow of a non-send resource in the subworld `W`.
+///
+/// Like bevy's `NonSendMut`, this forces the system to run on the main thread.
+pub struct OtherNonSendMut<'w, W: DerefMut<Target = World> + Component, T: 'static> {
+    value: &'w mut T,
+    ticks: &'w mut ComponentTicks,
+    last_change_tick: u32,
+    change_tick: u32,
+    w: PhantomData<W>,
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, T: 'static> OtherNonSendMut<'w, W, T> {
+    /// Returns true if (and only if) this resource been added since the last execution of this
+    /// system.
+    pub fn is_added(&self) -> bool {
+        self.ticks.is_added(self.last_change_tick, self.change_tick)
+    }
+
+    /// Returns true if (and only if) this resource been changed since the last execution of this
+    /// system.
+    pub fn is_changed(&self) -> bool {
+        self.ticks
+            .is_changed(self.last_change_tick, self.change_tick)
+    }
+
+    /// Manually flags this resource as changed in the subworld, as if it had been mutably
+    /// dereferenced.
+    pub fn set_changed(&mut self) {
+        self.ticks.set_changed(self.change_tick);
+    }
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: 'static> SystemParam for OtherNonSendMut<'a, W, T> {
+    type Fetch = OtherNonSendMutState<W, T>;
+}
+
+pub struct OtherNonSendMutState<W: DerefMut<Target = World> + Component, T: 'static> {
+    outer_component_id: ComponentId,
+    resource_ids: Vec<ComponentId>,
+    ticks: SubWorldTicks,
+    marker: PhantomData<(fn() -> T, W)>,
+}
+
+unsafe impl<W: DerefMut<Target = World> + Component, T: 'static> SystemParamState for OtherNonSendMutState<W, T> {
+    type Config = ();
+
+    fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
+        system_state.set_non_send();
+
+        let outer_component_id = world.initialize_resource::<Other<W, NonSendMarker<T>>>();
+        let world_id = world.initialize_resource::<W>();
+        if let Some(world) = get_subworld_mut::<W>(world) {
+            world.initialize_non_send_resource::<T>();
+        }
+        let nested_reads = nested_reads::<W>(world);
+        let resource_ids = subworld_resource_ids::<W>(world);
+        let combined_access = system_state.component_access_set.combined_access_mut();
+        if combined_access.has_write(outer_component_id) || combined_access.has_read(outer_component_id) || combined_access.has_read(world_id) || combined_access.has_write(world_id){
+            panic!(
+                "NonSendMut<{}, {}> in system {} conflicts with a Res<{0}> or ResMut{0} access. Allowing this would break Rust's mutability rules. Consider removing the duplicate access.",
+                std::any::type_name::<W>(), std::any::type_name::<T>(), system_state.name);
+        }
+        combined_access.add_write(outer_component_id);
+        for component_id in nested_reads{
+            if combined_access.has_write(component_id){
+                panic!(
+                    "NonSendMut<{}, {}> in system {} conflicts with mutable access to a world it is nested in. Allowing this would break Rust's mutability rules. Consider removing the duplicate access.",
+                    std::any::type_name::<W>(), std::any::type_name::<T>(), system_state.name);
+            }
+            combined_access.add_read(component_id);
+        }
+
+        Self {
+            outer_component_id,
+            resource_ids,
+            ticks: SubWorldTicks::default(),
+            marker: PhantomData,
+        }
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+        add_resource_archetype_access(&self.resource_ids, &[self.outer_component_id], archetype, system_state);
+    }
+
+    fn default_config() {}
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: 'static> SystemParamFetch<'a> for OtherNonSendMutState<W, T> {
+    type Item = OtherNonSendMut<'a, W, T>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'a mut Self,
+        system_state: &'a SystemState,
+        world: &'a World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        let world = get_subworld::<W>(world).expect("Couldn't find world!");
+        // NOTE: this also panics if the system somehow ended up off the main thread
+        let value = world
+            .get_non_send_resource_unchecked_mut::<T>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "Non-send resource requested by {} does not exist: {}",
+                    system_state.name,
+                    std::any::type_name::<T>()
+                )
+            });
+        let (last_change_tick, change_tick) = state.ticks.advance(world);
+
+        let (value, ticks) = value.value_and_ticks();
+        OtherNonSendMut {
+            value,
+            ticks,
+            last_change_tick,
+            change_tick,
+            w: PhantomData,
+        }
+    }
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, T: 'static> Deref for OtherNonSendMut<'w, W, T> {
+    type Target = T;
+
+    
+    fn deref(&self) -> &<Self as std::ops::Deref>::Target { 
+        &self.value
+    }
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, T: 'static> DerefMut for OtherNonSendMut<'w, W, T> {    
+    fn deref_mut(&mut self) -> &mut <Self as std::ops::Deref>::Target { 
+        self.ticks.set_changed(self.change_tick);
+        &mut self.value
+    }
+}
\ No newline at end of file
@@ -1,4 +1,4 @@
-use crate::other::Otherable;
+use crate::other_world_query::OtherWorldQuery;
 use bevy::ecs::world::Mut;
 use bevy::ecs::system::QueryComponentError;
 use core::any::TypeId;
@@ -16,25 +16,31 @@ use bevy::ecs::system::SystemParam;
 use bevy::ecs::query::FilterFetch;
 use bevy::ecs::component::Component;
 use bevy::ecs::world::World;
+use core::ops::Deref;
 use core::ops::DerefMut;
 use bevy::ecs::query::WorldQuery;
 use bevy::ecs::query::Fetch;
 use std::clone::Clone;
+use std::fmt;
 
 use crate::other_query_state::OtherQueryState;
+use crate::other_query_state::OptionOtherQueryState;
 use crate::other_query_iter::OtherQueryIter;
+use crate::other_query_iter::ArchetypeOnlyFilter;
+use crate::other_query_iter::OtherQueryCombinationIter;
+use crate::other_query_par_iter::OtherQueryParIter;
 
-pub struct OtherQuery<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + Otherable<W> + 'static, F: WorldQuery + 'static = ()>
+pub struct OtherQuery<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static = ()>
 where
     F::Fetch: FilterFetch,
 {
-    pub(crate) world: Mut<'w, W>,
+    pub(crate) world: &'w W,
     pub(crate) state: &'w OtherQueryState<W, Q, F>,
     pub(crate) last_change_tick: u32,
     pub(crate) change_tick: u32,
 }
 
-impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + Otherable<W> + 'static, F: WorldQuery + 'static> SystemParam for OtherQuery<'w, W, Q, F>
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParam for OtherQuery<'w, W, Q, F>
 where
     F::Fetch: FilterFetch,{
 
@@ -42,7 +48,7 @@ where
 }
 
 
-impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + Otherable<W>, F: WorldQuery> OtherQuery<'w, W, Q, F>
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>> OtherQuery<'w, W, Q, F>
 where
     F::Fetch: FilterFetch,
 {
@@ -51,7 +57,7 @@ where
     /// called in ways that ensure the Queries have unique mutable access.
     #[inline]
     pub(crate) unsafe fn new(
-        world: Mut<'w, W>,
+        world: &'w W,
         state: &'w OtherQueryState<W, Q, F>,
         last_change_tick: u32,
         change_tick: u32,
@@ -74,7 +80,7 @@ where
         // same-system queries have runtime borrow checks when they conflict
         unsafe {
             self.state
-                .iter_unchecked_manual(&self.world, self.last_change_tick, self.change_tick)
+                .iter_unchecked_manual(self.world, self.last_change_tick, self.change_tick)
         }
     }
 
@@ -85,7 +91,32 @@ where
         // same-system queries have runtime borrow checks when they conflict
         unsafe {
             self.state
-                .iter_unchecked_manual(&self.world, self.last_change_tick, self.change_tick)
+                .iter_unchecked_manual(self.world, self.last_change_tick, self.change_tick)
+        }
+    }
+
+    /// Iterates over every combination of `K` distinct query results. This can only be called for
+    /// read-only queries
+    #[inline]
+    pub fn iter_combinations<const K: usize>(&self) -> OtherQueryCombinationIter<'_, '_, W, Q, F, K>
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        // SAFE: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            OtherQueryCombinationIter::new(self.world, self.state, self.last_change_tick, self.change_tick)
+        }
+    }
+
+    /// Iterates over every combination of `K` distinct query results. Use
+    /// [`fetch_next`](OtherQueryCombinationIter::fetch_next) to advance it
+    #[inline]
+    pub fn iter_combinations_mut<const K: usize>(&mut self) -> OtherQueryCombinationIter<'_, '_, W, Q, F, K> {
+        // SAFE: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            OtherQueryCombinationIter::new(self.world, self.state, self.last_change_tick, self.change_tick)
         }
     }
 
@@ -99,7 +130,7 @@ where
         // SEMI-SAFE: system runs without conflicts with other systems.
         // same-system queries have runtime borrow checks when they conflict
         self.state
-            .iter_unchecked_manual(&self.world, self.last_change_tick, self.change_tick)
+            .iter_unchecked_manual(self.world, self.last_change_tick, self.change_tick)
     }
 
     /// Runs `f` on each query result. This is faster than the equivalent iter() method, but cannot
@@ -113,7 +144,7 @@ where
         // same-system queries have runtime borrow checks when they conflict
         unsafe {
             self.state.for_each_unchecked_manual(
-                &self.world,
+                self.world,
                 f,
                 self.last_change_tick,
                 self.change_tick,
@@ -129,7 +160,7 @@ where
         // borrow checks when they conflict
         unsafe {
             self.state.for_each_unchecked_manual(
-                &self.world,
+                self.world,
                 f,
                 self.last_change_tick,
                 self.change_tick,
@@ -150,8 +181,8 @@ where
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime
         // borrow checks when they conflict
         unsafe {
-            self.state.par_for_each_unchecked_manual(
-                &self.world,
+            self.state.par_for_each_unchecked_manual_from(
+                self.world,
                 task_pool,
                 batch_size,
                 f,
@@ -172,8 +203,8 @@ where
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime
         // borrow checks when they conflict
         unsafe {
-            self.state.par_for_each_unchecked_manual(
-                &self.world,
+            self.state.par_for_each_unchecked_manual_from(
+                self.world,
                 task_pool,
                 batch_size,
                 f,
@@ -183,6 +214,31 @@ where
         };
     }
 
+    /// Returns a [`ParallelIterator`](bevy::tasks::ParallelIterator) over the query results,
+    /// split into batches of at most `batch_size` entities. This can only be called for read-only queries
+    #[inline]
+    pub fn par_iter(&self, batch_size: usize) -> OtherQueryParIter<'_, '_, W, Q, F>
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        // SAFE: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            OtherQueryParIter::new(self.world, self.state, batch_size, self.last_change_tick, self.change_tick)
+        }
+    }
+
+    /// Returns a [`ParallelIterator`](bevy::tasks::ParallelIterator) over the query results,
+    /// split into batches of at most `batch_size` entities.
+    #[inline]
+    pub fn par_iter_mut(&mut self, batch_size: usize) -> OtherQueryParIter<'_, '_, W, Q, F> {
+        // SAFE: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            OtherQueryParIter::new(self.world, self.state, batch_size, self.last_change_tick, self.change_tick)
+        }
+    }
+
     /// Gets the query result for the given `entity`
     #[inline]
     pub fn get(&self, entity: Entity) -> Result<<Q::Fetch as Fetch>::Item, QueryEntityError>
@@ -192,8 +248,8 @@ where
         // SAFE: system runs without conflicts with other systems.
         // same-system queries have runtime borrow checks when they conflict
         unsafe {
-            self.state.get_unchecked_manual(
-                &self.world,
+            self.state.get_unchecked_manual_from(
+                self.world,
                 entity,
                 self.last_change_tick,
                 self.change_tick,
@@ -210,8 +266,8 @@ where
         // SAFE: system runs without conflicts with other systems.
         // same-system queries have runtime borrow checks when they conflict
         unsafe {
-            self.state.get_unchecked_manual(
-                &self.world,
+            self.state.get_unchecked_manual_from(
+                self.world,
                 entity,
                 self.last_change_tick,
                 self.change_tick,
@@ -219,6 +275,43 @@ where
         }
     }
 
+    /// Gets the query results for all of the given `entities` at once. This can only be called for
+    /// read-only queries
+    #[inline]
+    pub fn get_many<const N: usize>(&self, entities: [Entity; N]) -> Result<[<Q::Fetch as Fetch>::Item; N], OtherQueryManyError>
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        // SAFE: system runs without conflicts with other systems.
+        // same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state.get_many_unchecked_manual(
+                self.world,
+                entities,
+                true,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
+    /// Gets the query results for all of the given `entities` at once. Fails if any entity is
+    /// given more than once, since that would hand out aliased mutable references
+    #[inline]
+    pub fn get_many_mut<const N: usize>(&mut self, entities: [Entity; N]) -> Result<[<Q::Fetch as Fetch>::Item; N], OtherQueryManyError> {
+        // SAFE: system runs without conflicts with other systems, and the entities are checked to
+        // be distinct. same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state.get_many_unchecked_manual(
+                self.world,
+                entities,
+                false,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
     /// Gets the query result for the given `entity`
     ///
     /// # Safety
@@ -232,7 +325,7 @@ where
         // SEMI-SAFE: system runs without conflicts with other systems.
         // same-system queries have runtime borrow checks when they conflict
         self.state
-            .get_unchecked_manual(&self.world, entity, self.last_change_tick, self.change_tick)
+            .get_unchecked_manual_from(self.world, entity, self.last_change_tick, self.change_tick)
     }
 
     /// Gets a reference to the entity's component of the given type. This will fail if the entity
@@ -240,7 +333,7 @@ where
     /// this query.
     #[inline]
     pub fn get_component<T: Component>(&self, entity: Entity) -> Result<&T, QueryComponentError> {
-        let world = &self.world;
+        let world = self.world;
         let entity_ref = world
             .get_entity(entity)
             .ok_or(QueryComponentError::NoSuchEntity)?;
@@ -288,7 +381,7 @@ where
         &self,
         entity: Entity,
     ) -> Result<Mut<'_, T>, QueryComponentError> {
-        let world = &self.world;
+        let world = self.world;
         let entity_ref = world
             .get_entity(entity)
             .ok_or(QueryComponentError::NoSuchEntity)?;
@@ -313,6 +406,32 @@ where
         }
     }
 
+    /// Returns the number of entities matching the query. This is read from the matched archetypes
+    /// instead of iterating, so it is only available for archetype only filters
+    pub fn count(&self) -> usize
+    where
+        F: ArchetypeOnlyFilter,
+    {
+        let archetypes = self.world.archetypes();
+        self.state
+            .matched_archetype_ids
+            .iter()
+            .map(|archetype_id| archetypes[*archetype_id].len())
+            .sum()
+    }
+
+    /// Returns `true` if no entity matches the query. See [`count`](Self::count)
+    pub fn is_empty(&self) -> bool
+    where
+        F: ArchetypeOnlyFilter,
+    {
+        let archetypes = self.world.archetypes();
+        self.state
+            .matched_archetype_ids
+            .iter()
+            .all(|archetype_id| archetypes[*archetype_id].len() == 0)
+    }
+
     pub fn single(&self) -> Result<<Q::Fetch as Fetch<'_>>::Item, QuerySingleError>
     where
         Q::Fetch: ReadOnlyFetch,
@@ -344,4 +463,73 @@ where
             >())),
         }
     }
+}
+
+/// Error returned by [`OtherQuery::get_many`] and [`OtherQuery::get_many_mut`].
+#[derive(Debug)]
+pub enum OtherQueryManyError{
+    /// The query couldn't fetch this entity, because it doesn't exist or doesn't match.
+    QueryEntityError(Entity, QueryEntityError),
+    /// This entity was requested more than once from a mutable query.
+    AliasedMutability(Entity),
+}
+
+impl fmt::Display for OtherQueryManyError{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        match self{
+            OtherQueryManyError::QueryEntityError(entity, error) => write!(f, "couldn't get entity {:?}: {}", entity, error),
+            OtherQueryManyError::AliasedMutability(entity) => write!(f, "entity {:?} was requested mutably more than once", entity),
+        }
+    }
+}
+
+impl std::error::Error for OtherQueryManyError{}
+
+/// An [`OtherQuery`] that is `None` while the subworld `W` does not exist, instead of panicking.
+///
+/// The outer access is still registered up front, so systems using it are scheduled the same way
+/// whether or not the subworld has been inserted yet.
+///
+/// NOTE: the inner query state is set up the first time the query is fetched after the subworld
+/// is inserted or replaced, which needs exclusive access to the subworld. Because of that, systems
+/// using it never run in parallel with other systems accessing `W`, and it conflicts with other
+/// queries and commands of `W` in the same system.
+pub struct OptionOtherQuery<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static = ()>(pub(crate) Option<OtherQuery<'w, W, Q, F>>)
+where
+    F::Fetch: FilterFetch;
+
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> OptionOtherQuery<'w, W, Q, F>
+where
+    F::Fetch: FilterFetch,
+{
+    pub fn into_inner(self) -> Option<OtherQuery<'w, W, Q, F>> {
+        self.0
+    }
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParam for OptionOtherQuery<'w, W, Q, F>
+where
+    F::Fetch: FilterFetch,{
+
+    type Fetch = OptionOtherQueryState<W, Q, F>;
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> Deref for OptionOtherQuery<'w, W, Q, F>
+where
+    F::Fetch: FilterFetch,{
+
+    type Target = Option<OtherQuery<'w, W, Q, F>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> DerefMut for OptionOtherQuery<'w, W, Q, F>
+where
+    F::Fetch: FilterFetch,{
+
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
 }
\ No newline at end of file
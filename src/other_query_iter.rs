@@ -1,8 +1,7 @@
-use crate::other::Otherable;
+use crate::other_world_query::OtherWorldQuery;
 use bevy::ecs::component::Component;
 use bevy::ecs::world::World;
 use core::ops::DerefMut;
-use bevy::ecs::world::Mut;
 use bevy::ecs::archetype::ArchetypeId;
 use bevy::ecs::archetype::Archetypes;
 use bevy::ecs::storage::Tables;
@@ -10,17 +9,19 @@ use bevy::ecs::query::FilterFetch;
 use bevy::ecs::query::WorldQuery;
 use bevy::ecs::query::Fetch;
 use bevy::ecs::storage::TableId;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::ReadOnlyFetch;
+use core::mem::MaybeUninit;
 
 use crate::other_query_state::OtherQueryState;
 
-pub struct OtherQueryIter<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + Otherable<W>, F: WorldQuery>
+pub struct OtherQueryIter<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>>
 where
     F::Fetch: FilterFetch,
 {
     tables: &'w Tables,
     archetypes: &'w Archetypes,
     query_state: &'s OtherQueryState<W, Q, F>,
-    world: &'w Mut<'w, W>,
     table_id_iter: std::slice::Iter<'s, TableId>,
     archetype_id_iter: std::slice::Iter<'s, ArchetypeId>,
     fetch: Q::Fetch,
@@ -28,14 +29,17 @@ where
     is_dense: bool,
     current_len: usize,
     current_index: usize,
+    // NOTE: the rows of the matched archetypes that haven't been visited yet, whether they end up
+    // passing the filter or not
+    remaining: usize,
 }
 
-impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + Otherable<W>, F: WorldQuery> OtherQueryIter<'w, 's, W, Q, F>
+impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>> OtherQueryIter<'w, 's, W, Q, F>
 where
     F::Fetch: FilterFetch,
 {
     pub(crate) unsafe fn new(
-        world: &'w Mut<'w, W>,
+        world: &'w W,
         query_state: &'s OtherQueryState<W, Q, F>,
         last_change_tick: u32,
         change_tick: u32,
@@ -52,9 +56,14 @@ where
             last_change_tick,
             change_tick,
         );
+        let archetypes = world.archetypes();
+        let remaining = query_state
+            .matched_archetype_ids
+            .iter()
+            .map(|archetype_id| archetypes[*archetype_id].len())
+            .sum();
         OtherQueryIter {
             is_dense: fetch.is_dense() && filter.is_dense(),
-            world,
             query_state,
             fetch,
             filter,
@@ -64,11 +73,12 @@ where
             archetype_id_iter: query_state.matched_archetype_ids.iter(),
             current_len: 0,
             current_index: 0,
+            remaining,
         }
     }
 }
 
-impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + Otherable<W>, F: WorldQuery> Iterator for OtherQueryIter<'w, 's, W, Q, F>
+impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>> Iterator for OtherQueryIter<'w, 's, W, Q, F>
 where
     F::Fetch: FilterFetch,
 {
@@ -89,6 +99,7 @@ where
                         continue;
                     }
 
+                    self.remaining = self.remaining.saturating_sub(1);
                     if !self.filter.table_filter_fetch(self.current_index) {
                         self.current_index += 1;
                         continue;
@@ -119,6 +130,7 @@ where
                         continue;
                     }
 
+                    self.remaining = self.remaining.saturating_sub(1);
                     if !self.filter.archetype_filter_fetch(self.current_index) {
                         self.current_index += 1;
                         continue;
@@ -132,32 +144,173 @@ where
         }
     }
 
-    // NOTE: For unfiltered Queries this should actually return a exact size hint,
-    // to fulfil the ExactSizeIterator invariant, but this isn't practical without specialization.
-    // For more information see Issue #1686.
+    // NOTE: every remaining row passes an archetype only filter, so the hint is exact for those,
+    // which is what ExactSizeIterator::len relies on
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let max_size = self
-            .query_state
-            .matched_archetypes
-            .ones()
-            .map(|index| self.world.archetypes()[ArchetypeId::new(index)].len())
-            .sum();
-
-        (0, Some(max_size))
+        if F::IS_ARCHETYPE_FILTER {
+            (self.remaining, Some(self.remaining))
+        } else {
+            (0, Some(self.remaining))
+        }
     }
 }
 
-// NOTE: We can cheaply implement this for unfiltered Queries because we have:
+/// A filter that only depends on which components an entity has, so every entity in a matched
+/// archetype passes it. Implemented for [`With`](bevy::ecs::query::With),
+/// [`Without`](bevy::ecs::query::Without), and tuples or [`Or`](bevy::ecs::query::Or)s of those.
+///
+/// This lets [`OtherQueryIter`] know its exact length. It is sealed and only implemented next to
+/// the [`OtherWorldQuery`] impls that set [`OtherWorldQuery::IS_ARCHETYPE_FILTER`], which is what the
+/// iterator reads, so the two can't disagree.
+pub trait ArchetypeOnlyFilter: WorldQuery + sealed::Sealed {}
+
+pub(crate) mod sealed{
+    pub trait Sealed {}
+}
+
+// NOTE: We can cheaply implement this for archetype only filters because we have:
 // (1) pre-computed archetype matches
 // (2) each archetype pre-computes length
 // (3) there are no per-entity filters
-// TODO: add an ArchetypeOnlyFilter that enables us to implement this for filters like With<T>
-impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + Otherable<W>> ExactSizeIterator for OtherQueryIter<'w, 's, W, Q, ()> {
-    fn len(&self) -> usize {
-        self.query_state
-            .matched_archetypes
-            .ones()
-            .map(|index| self.world.archetypes()[ArchetypeId::new(index)].len())
-            .sum()
+impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W> + ArchetypeOnlyFilter> ExactSizeIterator for OtherQueryIter<'w, 's, W, Q, F>
+where
+    F::Fetch: FilterFetch,
+{}
+
+/// Iterates over every combination of `K` distinct results of an
+/// [`OtherQuery`](crate::other_query::OtherQuery), without repeating a combination in a different
+/// order.
+///
+/// Mutable queries can only be advanced with [`fetch_next`](Self::fetch_next), since items from
+/// different combinations can refer to the same entity.
+pub struct OtherQueryCombinationIter<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>, const K: usize>
+where
+    F::Fetch: FilterFetch,
+{
+    query_state: &'s OtherQueryState<W, Q, F>,
+    world: &'w W,
+    // NOTE: the entities that pass the filter are collected once from the matched archetypes, so
+    // every combination is only a set of indices into them
+    entities: Vec<Entity>,
+    indices: [usize; K],
+    started: bool,
+    finished: bool,
+    last_change_tick: u32,
+    change_tick: u32,
+}
+
+impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>, const K: usize> OtherQueryCombinationIter<'w, 's, W, Q, F, K>
+where
+    F::Fetch: FilterFetch,
+{
+    pub(crate) unsafe fn new(
+        world: &'w W,
+        query_state: &'s OtherQueryState<W, Q, F>,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        let mut filter = <F::Fetch as Fetch>::init(
+            &world,
+            &query_state.filter_state,
+            last_change_tick,
+            change_tick,
+        );
+        let tables = &world.storages().tables;
+        let archetypes = world.archetypes();
+        let mut entities = Vec::new();
+        for archetype_id in query_state.matched_archetype_ids.iter() {
+            let archetype = &archetypes[*archetype_id];
+            filter.set_archetype(&query_state.filter_state, archetype, tables);
+            for (archetype_index, entity) in archetype.entities().iter().enumerate() {
+                if filter.archetype_filter_fetch(archetype_index) {
+                    entities.push(*entity);
+                }
+            }
+        }
+        OtherQueryCombinationIter {
+            query_state,
+            world,
+            entities,
+            indices: [0; K],
+            started: false,
+            finished: false,
+            last_change_tick,
+            change_tick,
+        }
+    }
+
+    /// Returns the next combination. The items borrow the iterator, so they have to be dropped
+    /// before the next call. This makes it usable for mutable queries
+    #[inline]
+    pub fn fetch_next(&mut self) -> Option<[<Q::Fetch as Fetch<'_>>::Item; K]> {
+        // SAFE: the items can't outlive the call that returned them, and the K entities of a
+        // combination are always distinct
+        unsafe { self.fetch_next_aliased_unchecked() }
+    }
+
+    /// # Safety
+    /// The items of different combinations can refer to the same entity, so this must only be used
+    /// with read-only queries or with items that don't outlive the next call.
+    unsafe fn fetch_next_aliased_unchecked<'a>(&mut self) -> Option<[<Q::Fetch as Fetch<'a>>::Item; K]>
+    where
+        'w: 'a,
+    {
+        if !self.advance() {
+            return None;
+        }
+        let world: &'a W = self.world;
+        // SAFE: an array of MaybeUninit doesn't need to be initialized
+        let mut items: [MaybeUninit<<Q::Fetch as Fetch<'a>>::Item>; K] = MaybeUninit::uninit().assume_init();
+        for (item, index) in items.iter_mut().zip(self.indices.iter()) {
+            let entity = self.entities[*index];
+            *item = MaybeUninit::new(
+                self.query_state
+                    .get_unchecked_manual_from(world, entity, self.last_change_tick, self.change_tick)
+                    .expect("Entity passed the filter when the iterator was created"),
+            );
+        }
+        // SAFE: every item was initialized above
+        Some((&items as *const _ as *const [<Q::Fetch as Fetch<'a>>::Item; K]).read())
+    }
+
+    /// Moves on to the next set of indices, in lexicographic order.
+    fn advance(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+        let len = self.entities.len();
+        if !self.started {
+            self.started = true;
+            for (i, index) in self.indices.iter_mut().enumerate() {
+                *index = i;
+            }
+            self.finished = K == 0 || K > len;
+            return !self.finished;
+        }
+        for i in (0..K).rev() {
+            if self.indices[i] < len - K + i {
+                self.indices[i] += 1;
+                for j in i + 1..K {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+                return true;
+            }
+        }
+        self.finished = true;
+        false
+    }
+}
+
+impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>, const K: usize> Iterator for OtherQueryCombinationIter<'w, 's, W, Q, F, K>
+where
+    Q::Fetch: ReadOnlyFetch,
+    F::Fetch: FilterFetch,
+{
+    type Item = [<Q::Fetch as Fetch<'w>>::Item; K];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFE: the query is read only, so aliased items are fine
+        unsafe { self.fetch_next_aliased_unchecked() }
     }
 }
\ No newline at end of file
@@ -0,0 +1,198 @@
+use crate::other_world_query::OtherWorldQuery;
+use bevy::ecs::component::Component;
+use bevy::ecs::world::World;
+use core::ops::DerefMut;
+use core::ops::Range;
+use bevy::ecs::archetype::ArchetypeId;
+use bevy::ecs::query::FilterFetch;
+use bevy::ecs::query::WorldQuery;
+use bevy::ecs::query::Fetch;
+use bevy::ecs::storage::TableId;
+use bevy::tasks::ParallelIterator;
+
+use crate::other_query_state::OtherQueryState;
+
+/// A [`ParallelIterator`] over the results of an
+/// [`OtherQuery`](crate::other_query::OtherQuery), split into batches of at most `batch_size`
+/// entities from a single table or archetype.
+pub struct OtherQueryParIter<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>>
+where
+    F::Fetch: FilterFetch,
+{
+    world: &'w W,
+    query_state: &'s OtherQueryState<W, Q, F>,
+    batch_size: usize,
+    last_change_tick: u32,
+    change_tick: u32,
+    is_dense: bool,
+    // NOTE: index into either the matched tables or the matched archetypes, depending on is_dense
+    current_id: usize,
+    current_offset: usize,
+}
+
+impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>> OtherQueryParIter<'w, 's, W, Q, F>
+where
+    F::Fetch: FilterFetch,
+{
+    /// # Safety
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    pub(crate) unsafe fn new(
+        world: &'w W,
+        query_state: &'s OtherQueryState<W, Q, F>,
+        batch_size: usize,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        assert!(batch_size > 0, "batch_size has to be at least 1");
+        let fetch = <Q::Fetch as Fetch>::init(
+            &world,
+            &query_state.fetch_state,
+            last_change_tick,
+            change_tick,
+        );
+        let filter = <F::Fetch as Fetch>::init(
+            &world,
+            &query_state.filter_state,
+            last_change_tick,
+            change_tick,
+        );
+        OtherQueryParIter {
+            world,
+            query_state,
+            batch_size,
+            last_change_tick,
+            change_tick,
+            is_dense: fetch.is_dense() && filter.is_dense(),
+            current_id: 0,
+            current_offset: 0,
+        }
+    }
+}
+
+impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>> ParallelIterator<OtherQueryBatch<'w, 's, W, Q, F>> for OtherQueryParIter<'w, 's, W, Q, F>
+where
+    F::Fetch: FilterFetch,
+{
+    type Item = <Q::Fetch as Fetch<'w>>::Item;
+
+    fn next_batch(&mut self) -> Option<OtherQueryBatch<'w, 's, W, Q, F>> {
+        loop {
+            let (storage, len) = if self.is_dense {
+                let table_id = *self.query_state.matched_table_ids.get(self.current_id)?;
+                (BatchStorage::Table(table_id), self.world.storages().tables[table_id].len())
+            } else {
+                let archetype_id = *self.query_state.matched_archetype_ids.get(self.current_id)?;
+                (BatchStorage::Archetype(archetype_id), self.world.archetypes()[archetype_id].len())
+            };
+            if self.current_offset >= len {
+                self.current_id += 1;
+                self.current_offset = 0;
+                continue;
+            }
+            let start = self.current_offset;
+            self.current_offset += self.batch_size.min(len - start);
+            return Some(OtherQueryBatch {
+                world: self.world,
+                query_state: self.query_state,
+                storage,
+                range: start..self.current_offset,
+                last_change_tick: self.last_change_tick,
+                change_tick: self.change_tick,
+                fetch: None,
+            });
+        }
+    }
+}
+
+enum BatchStorage {
+    Table(TableId),
+    Archetype(ArchetypeId),
+}
+
+/// One batch of an [`OtherQueryParIter`].
+pub struct OtherQueryBatch<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>>
+where
+    F::Fetch: FilterFetch,
+{
+    world: &'w W,
+    query_state: &'s OtherQueryState<W, Q, F>,
+    storage: BatchStorage,
+    range: Range<usize>,
+    last_change_tick: u32,
+    change_tick: u32,
+    // NOTE: the fetches are only created once the batch starts iterating, which happens on the
+    // thread it was sent to
+    fetch: Option<(Q::Fetch, F::Fetch)>,
+}
+
+// SAFE: the fetches only point into storages of the subworld, which is borrowed for 'w, and every
+// batch covers a distinct range of entities, so batches never hand out aliasing items.
+unsafe impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>> Send for OtherQueryBatch<'w, 's, W, Q, F>
+where
+    F::Fetch: FilterFetch,
+{}
+
+impl<'w, 's, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>> Iterator for OtherQueryBatch<'w, 's, W, Q, F>
+where
+    F::Fetch: FilterFetch,
+{
+    type Item = <Q::Fetch as Fetch<'w>>::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            if self.fetch.is_none() {
+                let world: &'w World = self.world;
+                let mut fetch = <Q::Fetch as Fetch>::init(
+                    world,
+                    &self.query_state.fetch_state,
+                    self.last_change_tick,
+                    self.change_tick,
+                );
+                let mut filter = <F::Fetch as Fetch>::init(
+                    world,
+                    &self.query_state.filter_state,
+                    self.last_change_tick,
+                    self.change_tick,
+                );
+                let tables = &world.storages().tables;
+                match self.storage {
+                    BatchStorage::Table(table_id) => {
+                        let table = &tables[table_id];
+                        fetch.set_table(&self.query_state.fetch_state, table);
+                        filter.set_table(&self.query_state.filter_state, table);
+                    }
+                    BatchStorage::Archetype(archetype_id) => {
+                        let archetype = &world.archetypes()[archetype_id];
+                        fetch.set_archetype(&self.query_state.fetch_state, archetype, tables);
+                        filter.set_archetype(&self.query_state.filter_state, archetype, tables);
+                    }
+                }
+                self.fetch = Some((fetch, filter));
+            }
+            let (fetch, filter) = self.fetch.as_mut().unwrap();
+            loop {
+                let index = self.range.next()?;
+                match self.storage {
+                    BatchStorage::Table(_) => {
+                        if !filter.table_filter_fetch(index) {
+                            continue;
+                        }
+                        return Some(fetch.table_fetch(index));
+                    }
+                    BatchStorage::Archetype(_) => {
+                        if !filter.archetype_filter_fetch(index) {
+                            continue;
+                        }
+                        return Some(fetch.archetype_fetch(index));
+                    }
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.range.len()))
+    }
+}
\ No newline at end of file
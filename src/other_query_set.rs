@@ -0,0 +1,94 @@
+use crate::other_world_query::OtherWorldQuery;
+use bevy::ecs::archetype::Archetype;
+use bevy::ecs::system::SystemState;
+use bevy::ecs::system::SystemParamState;
+use bevy::ecs::system::SystemParamFetch;
+use bevy::ecs::system::SystemParam;
+use bevy::ecs::query::FilterFetch;
+use bevy::ecs::component::Component;
+use bevy::ecs::world::World;
+use core::ops::DerefMut;
+use bevy::ecs::query::WorldQuery;
+
+use crate::other_query::OtherQuery;
+use crate::other_query_state::OtherQueryState;
+
+/// A set of [`OtherQuery`]s over the same subworld that may conflict with each other.
+///
+/// Only one of the queries can be used at a time, so their accesses are registered as the union
+/// of all of them instead of being checked against each other.
+pub struct OtherQuerySet<T>(T);
+pub struct OtherQuerySetState<T>(T);
+
+macro_rules! impl_other_query_set{
+    ($(($query: ident, $filter: ident, $index: tt, $get: ident, $get_mut: ident)),*) => {
+        impl<'w, W: DerefMut<Target = World> + Component, $($query: WorldQuery + OtherWorldQuery<W> + 'static, $filter: WorldQuery + OtherWorldQuery<W> + 'static,)*> SystemParam for OtherQuerySet<($(OtherQuery<'w, W, $query, $filter>,)*)>
+        where
+            $($filter::Fetch: FilterFetch,)*
+        {
+            type Fetch = OtherQuerySetState<($(OtherQueryState<W, $query, $filter>,)*)>;
+        }
+
+        // SAFE: the union of the outer access of every query is applied to SystemState. If any of
+        // them conflicts with a previous system parameter, a panic will occur.
+        unsafe impl<W: DerefMut<Target = World> + Component, $($query: WorldQuery + OtherWorldQuery<W> + 'static, $filter: WorldQuery + OtherWorldQuery<W> + 'static,)*> SystemParamState for OtherQuerySetState<($(OtherQueryState<W, $query, $filter>,)*)>
+        where
+            $($filter::Fetch: FilterFetch,)*
+        {
+            type Config = ();
+
+            fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
+                let mut states = ($(OtherQueryState::<W, $query, $filter>::new(world),)*);
+                $(OtherQueryState::<W, $query, $filter>::assert_outer_access(&states.$index.outer_component_access, world, system_state);)*
+                $(OtherQueryState::<W, $query, $filter>::add_outer_access(&states.$index.outer_component_access, system_state);)*
+                $(states.$index.init_outer_archetype_component_access(world);)*
+                OtherQuerySetState(states)
+            }
+
+            fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+                let states = &self.0;
+                $(OtherQueryState::<W, $query, $filter>::add_outer_archetype_access(&states.$index.outer_resource_ids, &states.$index.outer_archetype_component_access, archetype, system_state);)*
+            }
+
+            fn default_config() {}
+        }
+
+        impl<'w, W: DerefMut<Target = World> + Component, $($query: WorldQuery + OtherWorldQuery<W> + 'static, $filter: WorldQuery + OtherWorldQuery<W> + 'static,)*> SystemParamFetch<'w> for OtherQuerySetState<($(OtherQueryState<W, $query, $filter>,)*)>
+        where
+            $($filter::Fetch: FilterFetch,)*
+        {
+            type Item = OtherQuerySet<($(OtherQuery<'w, W, $query, $filter>,)*)>;
+
+            #[inline]
+            unsafe fn get_param(
+                state: &'w mut Self,
+                _system_state: &'w SystemState,
+                world: &'w World,
+                _change_tick: u32,
+            ) -> Self::Item {
+                let states = &mut state.0;
+                OtherQuerySet(($(states.$index.get_other_query(world),)*))
+            }
+        }
+
+        impl<'w, W: DerefMut<Target = World> + Component, $($query: WorldQuery + OtherWorldQuery<W> + 'static, $filter: WorldQuery + OtherWorldQuery<W> + 'static,)*> OtherQuerySet<($(OtherQuery<'w, W, $query, $filter>,)*)>
+        where
+            $($filter::Fetch: FilterFetch,)*
+        {
+            $(
+                pub fn $get(&self) -> &OtherQuery<'w, W, $query, $filter> {
+                    &(self.0).$index
+                }
+
+                pub fn $get_mut(&mut self) -> &mut OtherQuery<'w, W, $query, $filter> {
+                    &mut (self.0).$index
+                }
+            )*
+        }
+    }
+}
+
+impl_other_query_set!((Q0, F0, 0, q0, q0_mut));
+impl_other_query_set!((Q0, F0, 0, q0, q0_mut), (Q1, F1, 1, q1, q1_mut));
+impl_other_query_set!((Q0, F0, 0, q0, q0_mut), (Q1, F1, 1, q1, q1_mut), (Q2, F2, 2, q2, q2_mut));
+impl_other_query_set!((Q0, F0, 0, q0, q0_mut), (Q1, F1, 1, q1, q1_mut), (Q2, F2, 2, q2, q2_mut), (Q3, F3, 3, q3, q3_mut));
\ No newline at end of file
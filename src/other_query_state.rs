@@ -1,15 +1,21 @@
 use bevy::ecs::query::FilteredAccessSet;
+use crate::nested::get_subworld;
+use crate::nested::get_subworld_mut;
+use crate::nested::get_subworld_unchecked_mut;
+use crate::nested::nested_reads;
+use crate::nested::subworld_resource_ids;
+use crate::nested::add_resource_archetype_access;
 use bevy::ecs::system::SystemParamState;
 use bevy::ecs::system::SystemState;
 use bevy::ecs::system::SystemParamFetch;
 use bevy::ecs::component::Component;
 use core::ops::DerefMut;
-use bevy::ecs::world::Mut;
 use bevy::tasks::TaskPool;
 use bevy::ecs::archetype::Archetype;
 use bevy::ecs::query::ReadOnlyFetch;
 use bevy::ecs::query::QueryEntityError;
 use bevy::ecs::entity::Entity;
+use bevy::ecs::world::Mut;
 use bevy::ecs::world::World;
 use bevy::ecs::archetype::ArchetypeId;
 use bevy::ecs::storage::TableId;
@@ -18,6 +24,9 @@ use bevy::ecs::query::FilteredAccess;
 use bevy::ecs::query::Access;
 use bevy::ecs::archetype::ArchetypeComponentId;
 use fixedbitset::FixedBitSet;
+use core::mem::MaybeUninit;
+use core::hash::Hash;
+use bevy::utils::HashMap;
 use bevy::ecs::archetype::ArchetypeGeneration;
 use bevy::ecs::world::WorldId;
 use bevy::ecs::query::FilterFetch;
@@ -27,30 +36,40 @@ use bevy::ecs::query::FetchState;
 
 use crate::other_query_iter::OtherQueryIter;
 use crate::other_query::OtherQuery;
+use crate::other_query::OptionOtherQuery;
+use crate::other_query::OtherQueryManyError;
 use crate::other::Other;
-use crate::other::Otherable;
+use crate::other::SubWorldMarker;
+use crate::other_ticks::SubWorldTicks;
+use crate::other_world_query::OtherWorldQuery;
+use crate::other_world_query::init_outer_archetype_component_access;
 
-pub struct OtherQueryState<W: DerefMut<Target = World> + Component, Q: WorldQuery + Otherable<W>, F: WorldQuery = ()>
+pub struct OtherQueryState<W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W> = ()>
 where
     F::Fetch: FilterFetch,
 {
-    world_id: WorldId,
+    pub(crate) world_id: WorldId,
     pub(crate) archetype_generation: ArchetypeGeneration,
     pub(crate) matched_tables: FixedBitSet,
     pub(crate) matched_archetypes: FixedBitSet,
     pub(crate) archetype_component_access: Access<ArchetypeComponentId>,
     pub(crate) component_access: FilteredAccess<ComponentId>,
     pub(crate) outer_component_access: FilteredAccess<ComponentId>,
+    // NOTE: the executor only looks at archetype level access, so the outer access of this query is
+    // expressed in ids of the outer world as well
+    pub(crate) outer_archetype_component_access: Access<ArchetypeComponentId>,
+    pub(crate) outer_resource_ids: Vec<ComponentId>,
     // NOTE: we maintain both a TableId bitset and a vec because iterating the vec is faster
     pub(crate) matched_table_ids: Vec<TableId>,
     // NOTE: we maintain both a ArchetypeId bitset and a vec because iterating the vec is faster
     pub(crate) matched_archetype_ids: Vec<ArchetypeId>,
     pub(crate) fetch_state: Q::State,
     pub(crate) filter_state: F::State,
+    ticks: SubWorldTicks,
     w: std::marker::PhantomData<W>
 }
 
-impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + Otherable<W> + 'static, F: WorldQuery + 'static> SystemParamFetch<'w> for OtherQueryState<W, Q, F>
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParamFetch<'w> for OtherQueryState<W, Q, F>
 where
     F::Fetch: FilterFetch,{
 
@@ -59,18 +78,15 @@ where
     #[inline]
     unsafe fn get_param(
         state: &'w mut Self,
-        system_state: &'w SystemState,
+        _system_state: &'w SystemState,
         world: &'w World,
-        change_tick: u32,
+        _change_tick: u32,
     ) -> Self::Item {
-        let last_change_tick = world.last_change_tick();
-        let change_tick = world.read_change_tick();
-        let world = world.get_resource_unchecked_mut::<W>().expect("Couldn't find world!");
-        OtherQuery::new(world, state, last_change_tick, change_tick)
+        state.get_other_query(world)
     }
 }
 
-unsafe impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + Otherable<W> + 'static, F: WorldQuery + 'static> SystemParamState for OtherQueryState<W, Q, F>
+unsafe impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParamState for OtherQueryState<W, Q, F>
 where
     F::Fetch: FilterFetch,{
 
@@ -78,39 +94,242 @@ where
     
 
     fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
-        let state = OtherQueryState::new(world);
+        let mut state = OtherQueryState::new(world);
+        Self::assert_outer_access(&state.outer_component_access, world, system_state);
+        Self::add_outer_access(&state.outer_component_access, system_state);
+        state.init_outer_archetype_component_access(world);
+        state
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+        Self::add_outer_archetype_access(&self.outer_resource_ids, &self.outer_archetype_component_access, archetype, system_state);
+    }
+
+    fn default_config() {}
+
+}
+
+impl<W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> OtherQueryState<W, Q, F>
+where
+    F::Fetch: FilterFetch,
+{
+    /// Panics if the outer access of this query conflicts with the access already in `system_state`.
+    pub(crate) fn assert_outer_access(outer_component_access: &FilteredAccess<ComponentId>, world: &mut World, system_state: &SystemState) {
         assert_component_access_compatibility(
             &system_state.name,
             std::any::type_name::<Other<W, Q>>(),
             std::any::type_name::<Other<W, F>>(),
             &system_state.component_access_set,
-            &state.component_access,
+            outer_component_access,
             world,
         );
 
         let world_id = world.initialize_resource::<W>();
-        
-        let combined_access = system_state.component_access_set.combined_access_mut();
+
+        let combined_access = system_state.component_access_set.combined_access();
 
         if combined_access.has_read(world_id) || combined_access.has_write(world_id){
             panic!(
                 "OtherQuery<{}, ...> in system {} conflicts with a Res<{0}> or ResMut<{0}> access. Allowing this would break Rust's mutability rules. Consider removing the duplicate access.",
                 std::any::type_name::<W>(), system_state.name);
         }
-        
+    }
+
+    pub(crate) fn add_outer_access(outer_component_access: &FilteredAccess<ComponentId>, system_state: &mut SystemState) {
         system_state
             .component_access_set
-            .add(state.outer_component_access.clone());
+            .add(outer_component_access.clone());
+    }
 
-        state
+    /// Adds the archetype level access of this query to `system_state`, which is what the executor
+    /// uses to decide which systems can run in parallel.
+    ///
+    /// The subworld access doesn't belong to any outer archetype, so it is added along with the
+    /// first one the executor hands out.
+    pub(crate) fn add_outer_archetype_access(outer_resource_ids: &[ComponentId], outer_archetype_component_access: &Access<ArchetypeComponentId>, archetype: &Archetype, system_state: &mut SystemState) {
+        add_resource_archetype_access(outer_resource_ids, &[], archetype, system_state);
+        system_state
+            .archetype_component_access
+            .extend(outer_archetype_component_access);
+    }
+
+    /// Registers this query with the other queries over the subworld, so the executor keeps it apart
+    /// from the ones it conflicts with.
+    pub(crate) fn init_outer_archetype_component_access(&mut self, world: &mut World) {
+        self.outer_archetype_component_access = init_outer_archetype_component_access::<W>(world, &self.outer_component_access);
+    }
+
+    /// # Safety
+    /// This does not check for mutable query correctness. To be safe, make sure the outer access of
+    /// this state has been registered with the system running the query.
+    pub(crate) unsafe fn get_other_query<'w>(&'w mut self, world: &'w World) -> OtherQuery<'w, W, Q, F> {
+        let world = get_subworld::<W>(world).expect("Couldn't find world!");
+        self.get_other_query_from(world)
+    }
+
+    /// Like [`get_other_query`](Self::get_other_query), but for a subworld that was already fetched.
+    ///
+    /// # Safety
+    /// Same as [`get_other_query`](Self::get_other_query).
+    pub(crate) unsafe fn get_other_query_from<'w>(&'w mut self, world: &'w W) -> OtherQuery<'w, W, Q, F> {
+        // NOTE: new archetypes in the subworld never show up through SystemParamState::new_archetype,
+        // which is only called for archetypes of the outer world, so they are picked up here instead
+        self.validate_world_and_update_archetypes(&world);
+        let (last_change_tick, change_tick) = self.ticks.advance(world);
+        OtherQuery::new(world, self, last_change_tick, change_tick)
+    }
+}
+
+pub struct OptionOtherQueryState<W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W> = ()>
+where
+    F::Fetch: FilterFetch,
+{
+    outer_component_access: FilteredAccess<ComponentId>,
+    resource_ids: Vec<ComponentId>,
+    // NOTE: the query is set up the first time it is fetched after the subworld appears, which is
+    // why the subworld is borrowed mutably
+    state: Option<OtherQueryState<W, Q, F>>,
+}
+
+// SAFE: the outer access of the query is applied to SystemState whether or not the subworld
+// exists yet, along with mutable access to the subworld and the worlds it is nested in. If it
+// conflicts with any prior access, a panic will occur.
+unsafe impl<W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParamState for OptionOtherQueryState<W, Q, F>
+where
+    F::Fetch: FilterFetch,{
+
+    type Config = ();
+
+    fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
+        let outer_component_access = OtherQueryState::<W, Q, F>::init_outer_component_access(world);
+        OtherQueryState::<W, Q, F>::assert_outer_access(&outer_component_access, world, system_state);
+        let mut writes = nested_reads::<W>(world);
+        writes.push(world.initialize_resource::<Other<W, SubWorldMarker>>());
+        let combined_access = system_state.component_access_set.combined_access();
+        if writes.iter().any(|component_id| combined_access.has_read(*component_id) || combined_access.has_write(*component_id)){
+            panic!(
+                "OptionOtherQuery<{}, ...> in system {} conflicts with another param holding on to {0} or a world it is nested in. Allowing this would break Rust's mutability rules. Consider removing the duplicate access.",
+                std::any::type_name::<W>(), system_state.name);
+        }
+        OtherQueryState::<W, Q, F>::add_outer_access(&outer_component_access, system_state);
+        let combined_access = system_state.component_access_set.combined_access_mut();
+        for component_id in writes {
+            combined_access.add_write(component_id);
+        }
+        Self {
+            outer_component_access,
+            resource_ids: subworld_resource_ids::<W>(world),
+            state: None,
+        }
     }
 
     fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
-        // THIS MAY NEED AN IMPL IN THE FUTURE
+        add_resource_archetype_access(&[], &self.resource_ids, archetype, system_state);
     }
 
     fn default_config() {}
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParamFetch<'w> for OptionOtherQueryState<W, Q, F>
+where
+    F::Fetch: FilterFetch,{
+
+    type Item = OptionOtherQuery<'w, W, Q, F>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'w mut Self,
+        _system_state: &'w SystemState,
+        world: &'w World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        let world_id = match get_subworld::<W>(world) {
+            Some(other) => other.id(),
+            None => return OptionOtherQuery(None),
+        };
+        if state.state.as_ref().map_or(true, |state| state.world_id != world_id) {
+            // SAFE: the subworld and every world it is nested in are borrowed mutably by this param
+            let other = get_subworld_unchecked_mut::<W>(world).unwrap();
+            state.state = Some(OtherQueryState::with_outer_access(state.outer_component_access.clone(), other));
+        }
+        let other = get_subworld::<W>(world).unwrap();
+        OptionOtherQuery(state.state.as_mut().map(|state| state.get_other_query_from(other)))
+    }
+}
 
+/// The states of one query over many subworlds of the type `W`, each found by a key `K`.
+///
+/// Params using this borrow the storage of the subworlds mutably, so the query can be set up for new
+/// subworlds while fetching.
+pub(crate) struct OtherQueryStates<K, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>>
+where
+    F::Fetch: FilterFetch,
+{
+    outer_component_access: FilteredAccess<ComponentId>,
+    storage_id: ComponentId,
+    // NOTE: every subworld has its own component ids and archetypes, so each one gets its own state
+    states: HashMap<K, OtherQueryState<W, Q, F>>,
+}
+
+impl<K: Copy + Eq + Hash + Ord, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> OtherQueryStates<K, W, Q, F>
+where
+    F::Fetch: FilterFetch,
+{
+    /// Applies the outer access of the query and a write of `storage_id`, the resource or component
+    /// the subworlds are stored in, to `system_state`. Panics if it conflicts with a prior query.
+    pub(crate) fn init(world: &mut World, system_state: &mut SystemState, storage_id: ComponentId) -> Self {
+        let mut outer_component_access = OtherQueryState::<W, Q, F>::init_outer_component_access(world);
+        outer_component_access.add_write(storage_id);
+        OtherQueryState::<W, Q, F>::assert_outer_access(&outer_component_access, world, system_state);
+        OtherQueryState::<W, Q, F>::add_outer_access(&outer_component_access, system_state);
+        Self {
+            outer_component_access,
+            storage_id,
+            states: HashMap::default(),
+        }
+    }
+
+    /// Adds the write of the storage of the subworlds to `system_state` at archetype level.
+    pub(crate) fn new_archetype(&self, archetype: &Archetype, system_state: &mut SystemState) {
+        add_resource_archetype_access(&[], &[self.storage_id], archetype, system_state);
+    }
+
+    /// Drops the states of subworlds that are gone or were replaced. `world_id` looks up the id of
+    /// the subworld found by a key.
+    pub(crate) fn retain(&mut self, world_id: impl Fn(K) -> Option<WorldId>) {
+        self.states.retain(|key, state| world_id(*key) == Some(state.world_id));
+    }
+
+    pub(crate) fn contains(&self, key: K) -> bool {
+        self.states.contains_key(&key)
+    }
+
+    /// Sets up the query for the subworld `world`, found by `key`.
+    pub(crate) fn insert(&mut self, key: K, world: &mut World) {
+        let state = OtherQueryState::with_outer_access(self.outer_component_access.clone(), world);
+        self.states.insert(key, state);
+    }
+
+    /// The queries over every subworld `get` still finds, sorted by key so the order doesn't depend
+    /// on the HashMap.
+    ///
+    /// # Safety
+    /// The storage of the subworlds has to be borrowed mutably by the system running the queries.
+    pub(crate) unsafe fn get_other_queries<'w>(&'w mut self, get: impl Fn(K) -> Option<&'w W>) -> Vec<(K, OtherQuery<'w, W, Q, F>)> {
+        let mut queries = self
+            .states
+            .iter_mut()
+            .filter_map(|(key, state)| {
+                let other = get(*key)?;
+                if other.id() != state.world_id {
+                    return None;
+                }
+                Some((*key, state.get_other_query_from(other)))
+            })
+            .collect::<Vec<_>>();
+        queries.sort_by_key(|(key, _)| *key);
+        queries
+    }
 }
 
 fn assert_component_access_compatibility(
@@ -130,29 +349,35 @@ fn assert_component_access_compatibility(
         .map(|component_id| world.components().get_info(component_id).unwrap().name())
         .collect::<Vec<&str>>();
     let accesses = conflicting_components.join(", ");
-    panic!("Query<{}, {}> in system {} accesses component(s) {} in a way that conflicts with a previous system parameter. Allowing this would break Rust's mutability rules. Consider merging conflicting Queries into a QuerySet.",
+    panic!("Query<{}, {}> in system {} accesses component(s) {} in a way that conflicts with a previous system parameter. Allowing this would break Rust's mutability rules. Consider merging conflicting OtherQueries into an OtherQuerySet.",
                 query_type, filter_type, system_name, accesses);
 }
 
-impl<W: DerefMut<Target = World> + Component, Q: WorldQuery + Otherable<W>, F: WorldQuery> OtherQueryState<W, Q, F>
+impl<W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W>> OtherQueryState<W, Q, F>
 where
     F::Fetch: FilterFetch,
 {
     pub fn new(world: &mut World) -> Self {
-        let mut world = unsafe{ world.get_resource_unchecked_mut::<W>().expect("Couldn't access world!") };
-        let fetch_state = <Q::State as FetchState>::init(&mut world);
-        let filter_state = <F::State as FetchState>::init(&mut world);
+        let outer_component_access = Self::init_outer_component_access(world);
+        let outer_resource_ids = Self::init_outer_resource_ids(world);
 
-        let outer_fetch_state = <Q::OtherState as FetchState>::init(&mut world);
+        let other = get_subworld_mut::<W>(world).expect("Couldn't access world!");
+        let mut state = Self::with_outer_access(outer_component_access, other);
+        state.outer_resource_ids = outer_resource_ids;
+        state
+    }
+
+    /// Creates the state for the inner `world` of one subworld, reusing access that was already
+    /// registered in the outer world.
+    pub(crate) fn with_outer_access(outer_component_access: FilteredAccess<ComponentId>, world: &mut World) -> Self {
+        let fetch_state = <Q::State as FetchState>::init(world);
+        let filter_state = <F::State as FetchState>::init(world);
 
         let mut component_access = Default::default();
-        let mut outer_component_access = Default::default();
 
         fetch_state.update_component_access(&mut component_access);
         filter_state.update_component_access(&mut component_access);
 
-        outer_fetch_state.update_component_access(&mut outer_component_access);
-
         let mut state = Self {
             world_id: world.id(),
             archetype_generation: ArchetypeGeneration::new(usize::MAX),
@@ -162,15 +387,40 @@ where
             filter_state,
             component_access,
             outer_component_access,
+            outer_archetype_component_access: Default::default(),
+            outer_resource_ids: Vec::new(),
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             archetype_component_access: Default::default(),
+            ticks: SubWorldTicks::default(),
             w: std::marker::PhantomData,
         };
-        state.validate_world_and_update_archetypes(&world);
+        state.validate_world_and_update_archetypes(world);
         state
     }
 
+    /// Registers the Other<W, T> markers of this query in the outer world and returns its access
+    /// in terms of them. This does not need the subworld to exist.
+    pub(crate) fn init_outer_component_access(world: &mut World) -> FilteredAccess<ComponentId> {
+        // NOTE: the Other<W, T> markers have to be registered in the outer world, since that is the
+        // world the system state (and therefore the scheduler) checks access against
+        let mut outer_component_access = Default::default();
+
+        Q::update_outer_component_access(world, &mut outer_component_access);
+        F::update_outer_component_access(world, &mut outer_component_access);
+        for component_id in nested_reads::<W>(world) {
+            outer_component_access.add_read(component_id);
+        }
+        outer_component_access.add_read(world.initialize_resource::<Other<W, SubWorldMarker>>());
+
+        outer_component_access
+    }
+
+    /// The resources of the outer world the subworld is reached through.
+    pub(crate) fn init_outer_resource_ids(world: &mut World) -> Vec<ComponentId> {
+        subworld_resource_ids::<W>(world)
+    }
+
     pub fn validate_world_and_update_archetypes(&mut self, world: &World) {
         if world.id() != self.world_id {
             panic!("Attempted to use {} with a mismatched World. QueryStates can only be used with the World they were created from.",
@@ -266,6 +516,21 @@ where
         entity: Entity,
         last_change_tick: u32,
         change_tick: u32,
+    ) -> Result<<Q::Fetch as Fetch<'w>>::Item, QueryEntityError> {
+        self.get_unchecked_manual_from(world, entity, last_change_tick, change_tick)
+    }
+
+    /// Like [`get_unchecked_manual`](Self::get_unchecked_manual), but for a subworld that was
+    /// already fetched.
+    ///
+    /// # Safety
+    /// Same as [`get_unchecked_manual`](Self::get_unchecked_manual).
+    pub(crate) unsafe fn get_unchecked_manual_from<'w>(
+        &self,
+        world: &'w W,
+        entity: Entity,
+        last_change_tick: u32,
+        change_tick: u32,
     ) -> Result<<Q::Fetch as Fetch<'w>>::Item, QueryEntityError> {
         let location = world
             .entities()
@@ -292,6 +557,46 @@ where
         }
     }
 
+    /// Fetches the query results for all of `entities` at once. Duplicate entities are rejected
+    /// unless `allow_duplicates` is set.
+    ///
+    /// # Safety
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query, and only allow duplicates for read-only
+    /// queries.
+    pub(crate) unsafe fn get_many_unchecked_manual<'w, const N: usize>(
+        &self,
+        world: &'w W,
+        entities: [Entity; N],
+        allow_duplicates: bool,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Result<[<Q::Fetch as Fetch<'w>>::Item; N], OtherQueryManyError> {
+        if !allow_duplicates {
+            for (i, entity) in entities.iter().enumerate() {
+                if entities[..i].contains(entity) {
+                    return Err(OtherQueryManyError::AliasedMutability(*entity));
+                }
+            }
+        }
+        // NOTE: every entity is checked before any item is created, so an error never leaves
+        // part of the array initialized
+        for entity in entities.iter() {
+            self.get_unchecked_manual_from(world, *entity, last_change_tick, change_tick)
+                .map_err(|error| OtherQueryManyError::QueryEntityError(*entity, error))?;
+        }
+        // SAFE: an array of MaybeUninit doesn't need to be initialized
+        let mut items: [MaybeUninit<<Q::Fetch as Fetch<'w>>::Item>; N] = MaybeUninit::uninit().assume_init();
+        for (item, entity) in items.iter_mut().zip(entities.iter()) {
+            *item = MaybeUninit::new(
+                self.get_unchecked_manual_from(world, *entity, last_change_tick, change_tick)
+                    .expect("Entity was checked above"),
+            );
+        }
+        // SAFE: every item was initialized above
+        Ok((&items as *const _ as *const [<Q::Fetch as Fetch<'w>>::Item; N]).read())
+    }
+
     #[inline]
     pub fn iter<'w, 's>(&'s mut self, world: &'w Mut<'w, W>) -> OtherQueryIter<'w, 's, W, Q, F>
     where
@@ -327,7 +632,7 @@ where
     #[inline]
     pub(crate) unsafe fn iter_unchecked_manual<'w, 's>(
         &'s self,
-        world: &'w Mut<'w, W>,
+        world: &'w W,
         last_change_tick: u32,
         change_tick: u32,
     ) -> OtherQueryIter<'w, 's, W, Q, F> {
@@ -437,7 +742,7 @@ where
     /// with a mismatched WorldId is unsafe.
     pub(crate) unsafe fn for_each_unchecked_manual<'w, 's>(
         &'s self,
-        world: &'w Mut<'w, W>,
+        world: &'w W,
         mut func: impl FnMut(<Q::Fetch as Fetch<'w>>::Item),
         last_change_tick: u32,
         change_tick: u32,
@@ -492,6 +797,23 @@ where
         func: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
         last_change_tick: u32,
         change_tick: u32,
+    ) {
+        self.par_for_each_unchecked_manual_from(world, task_pool, batch_size, func, last_change_tick, change_tick);
+    }
+
+    /// Like [`par_for_each_unchecked_manual`](Self::par_for_each_unchecked_manual), but for a
+    /// subworld that was already fetched.
+    ///
+    /// # Safety
+    /// Same as [`par_for_each_unchecked_manual`](Self::par_for_each_unchecked_manual).
+    pub(crate) unsafe fn par_for_each_unchecked_manual_from<'w, 's>(
+        &'s self,
+        world: &'w W,
+        task_pool: &TaskPool,
+        batch_size: usize,
+        func: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
+        last_change_tick: u32,
+        change_tick: u32,
     ) {
         task_pool.scope(|scope| {
             let fetch =
@@ -559,8 +881,8 @@ where
                             let archetype = &world.archetypes()[*archetype_id];
                             fetch.set_archetype(&self.fetch_state, archetype, tables);
                             filter.set_archetype(&self.filter_state, archetype, tables);
-
-                            for archetype_index in 0..archetype.len() {
+                            let len = batch_size.min(archetype.len() - offset);
+                            for archetype_index in offset..offset + len {
                                 if !filter.archetype_filter_fetch(archetype_index) {
                                     continue;
                                 }
@@ -1,12 +1,21 @@
 use core::ops::Deref;
+use crate::nested::get_subworld;
+use crate::nested::get_subworld_mut;
+use crate::nested::nested_reads;
+use crate::nested::subworld_resource_ids;
+use crate::nested::add_resource_archetype_access;
+use core::any::TypeId;
 use crate::other::Other;
+use crate::other_ticks::SubWorldTicks;
 use bevy::ecs::system::SystemParamFetch;
 use bevy::ecs::system::SystemParamState;
 use bevy::ecs::system::SystemState;
 use core::marker::PhantomData;
 use bevy::ecs::component::ComponentId;
+use bevy::ecs::archetype::Archetype;
 use bevy::ecs::system::SystemParam;
 use bevy::ecs::world::World;
+use bevy::ecs::world::WorldId;
 use core::ops::DerefMut;
 use bevy::ecs::component::Component;
 use bevy::ecs::component::ComponentTicks;
@@ -39,7 +48,13 @@ impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParam for
 }
 
 pub struct OtherResState<W: DerefMut<Target = World> + Component, T> {
-    component_id: ComponentId,
+    // NOTE: the subworld might not exist yet when the system is initialized, in which case the id
+    // of the resource is looked up the first time it is fetched. The id is only valid for the world
+    // it was looked up in, so it's looked up again whenever the subworld is replaced.
+    component_id: Option<(WorldId, ComponentId)>,
+    // NOTE: the subworld resources and the Other<W, T> marker, read at the archetype level as well
+    reads: Vec<ComponentId>,
+    ticks: SubWorldTicks,
     marker: PhantomData<(T, W)>,
 }
 
@@ -49,8 +64,11 @@ unsafe impl<W: DerefMut<Target = World> + Component, T: Component> SystemParamSt
     fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
         let outer_component_id = world.initialize_resource::<Other<W, T>>();
         let world_id = world.initialize_resource::<W>();
-        let mut world = unsafe{ world.get_resource_unchecked_mut::<W>().expect("Couldn't find world!") };
-        let component_id = world.initialize_resource::<T>();
+        let component_id = get_subworld_mut::<W>(world)
+            .map(|world| (world.id(), world.initialize_resource::<T>()));
+        let nested_reads = nested_reads::<W>(world);
+        let mut reads = subworld_resource_ids::<W>(world);
+        reads.push(outer_component_id);
         let combined_access = system_state.component_access_set.combined_access_mut();
         if combined_access.has_write(outer_component_id) || combined_access.has_read(world_id) || combined_access.has_write(world_id){
             panic!(
@@ -58,16 +76,56 @@ unsafe impl<W: DerefMut<Target = World> + Component, T: Component> SystemParamSt
                 std::any::type_name::<W>(), std::any::type_name::<T>(), system_state.name);
         }
         combined_access.add_read(outer_component_id);
+        for component_id in nested_reads{
+            if combined_access.has_write(component_id){
+                panic!(
+                    "Res<{}, {}> in system {} conflicts with mutable access to a world it is nested in. Allowing this would break Rust's mutability rules. Consider removing the duplicate access.",
+                    std::any::type_name::<W>(), std::any::type_name::<T>(), system_state.name);
+            }
+            combined_access.add_read(component_id);
+        }
 
         Self {
             component_id,
+            reads,
+            ticks: SubWorldTicks::default(),
             marker: PhantomData,
         }
     }
 
+    fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+        add_resource_archetype_access(&self.reads, &[], archetype, system_state);
+    }
+
     fn default_config() {}
 }
 
+impl<W: DerefMut<Target = World> + Component, T: Component> OtherResState<W, T> {
+    /// Fetches the resource from the subworld, returning `None` if it does not exist.
+    ///
+    /// # Safety
+    /// The access of this state has to be registered with the system fetching the resource.
+    pub(crate) unsafe fn get_other_res<'a>(&'a mut self, world: &'a W) -> Option<OtherRes<'a, W, T>> {
+        let component_id = match self.component_id {
+            Some((world_id, component_id)) if world_id == world.id() => component_id,
+            _ => {
+                let component_id = world.components().get_resource_id(TypeId::of::<T>())?;
+                self.component_id = Some((world.id(), component_id));
+                component_id
+            }
+        };
+        let column = world.get_populated_resource_column(component_id)?;
+        let (last_change_tick, change_tick) = self.ticks.advance(world);
+        Some(OtherRes {
+            value: &*column.get_ptr().as_ptr().cast::<T>(),
+            ticks: &*column.get_ticks_mut_ptr(),
+            last_change_tick,
+            change_tick,
+            w: PhantomData
+        })
+    }
+}
+
 impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParamFetch<'a> for OtherResState<W, T> {
     type Item = OtherRes<'a, W, T>;
 
@@ -76,25 +134,75 @@ impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParamFetch
         state: &'a mut Self,
         system_state: &'a SystemState,
         world: &'a World,
-        change_tick: u32,
+        _change_tick: u32,
     ) -> Self::Item {
-        let mut world = world.get_resource_unchecked_mut::<W>().expect("Couldn't find world!");
-        let column = world
-            .get_populated_resource_column(state.component_id)
+        let world = get_subworld::<W>(world).expect("Couldn't find world!");
+        state
+            .get_other_res(world)
             .unwrap_or_else(|| {
                 panic!(
                     "Resource requested by {} does not exist: {}",
                     system_state.name,
                     std::any::type_name::<T>()
                 )
-            });
-        OtherRes {
-            value: &*column.get_ptr().as_ptr().cast::<T>(),
-            ticks: &*column.get_ticks_mut_ptr(),
-            last_change_tick: system_state.last_change_tick,
-            change_tick,
-            w: PhantomData
-        }
+            })
+    }
+}
+
+/// Shared borrow of a resource in the subworld `W` that might not exist.
+///
+/// Holds `None` if either the subworld or the resource is missing, instead of panicking like
+/// [`OtherRes`] does.
+// NOTE: SystemParam can't be implemented for Option<OtherRes> outside of bevy, so this wraps it
+pub struct OptionOtherRes<'w, W: DerefMut<Target = World> + Component, T: Component>(Option<OtherRes<'w, W, T>>);
+
+impl<'w, W: DerefMut<Target = World> + Component, T: Component> OptionOtherRes<'w, W, T> {
+    pub fn into_inner(self) -> Option<OtherRes<'w, W, T>> {
+        self.0
+    }
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParam for OptionOtherRes<'a, W, T> {
+    type Fetch = OptionOtherResState<W, T>;
+}
+
+pub struct OptionOtherResState<W: DerefMut<Target = World> + Component, T>(OtherResState<W, T>);
+
+unsafe impl<W: DerefMut<Target = World> + Component, T: Component> SystemParamState for OptionOtherResState<W, T> {
+    type Config = ();
+
+    fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
+        Self(OtherResState::init(world, system_state, ()))
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+        self.0.new_archetype(archetype, system_state);
+    }
+
+    fn default_config() {}
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParamFetch<'a> for OptionOtherResState<W, T> {
+    type Item = OptionOtherRes<'a, W, T>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'a mut Self,
+        _system_state: &'a SystemState,
+        world: &'a World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        let state = &mut state.0;
+        OptionOtherRes(get_subworld::<W>(world)
+            .and_then(|world| state.get_other_res(world)))
+    }
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, T: Component> Deref for OptionOtherRes<'w, W, T> {
+    type Target = Option<OtherRes<'w, W, T>>;
+
+    fn deref(&self) -> &<Self as std::ops::Deref>::Target {
+        &self.0
     }
 }
 
@@ -1,20 +1,29 @@
 use bevy::ecs::world::Mut;
+use crate::nested::get_subworld;
+use crate::nested::get_subworld_mut;
+use crate::nested::nested_reads;
+use crate::nested::subworld_resource_ids;
+use crate::nested::add_resource_archetype_access;
 use core::ops::Deref;
+use core::any::TypeId;
 use crate::other::Other;
+use crate::other_ticks::SubWorldTicks;
 use bevy::ecs::system::SystemParamFetch;
 use bevy::ecs::system::SystemParamState;
 use bevy::ecs::system::SystemState;
 use core::marker::PhantomData;
 use bevy::ecs::component::ComponentId;
+use bevy::ecs::archetype::Archetype;
 use bevy::ecs::system::SystemParam;
 use bevy::ecs::world::World;
+use bevy::ecs::world::WorldId;
 use core::ops::DerefMut;
 use bevy::ecs::component::Component;
 use bevy::ecs::component::ComponentTicks;
 
 pub struct OtherResMut<'w, W: DerefMut<Target = World> + Component, T: Component> {
     value: &'w mut T,
-    ticks: &'w ComponentTicks,
+    ticks: &'w mut ComponentTicks,
     last_change_tick: u32,
     change_tick: u32,
     world: &'w W,
@@ -33,6 +42,18 @@ impl<'w, W: DerefMut<Target = World> + Component, T: Component> OtherResMut<'w,
         self.ticks
             .is_changed(self.last_change_tick, self.change_tick)
     }
+
+    /// Manually flags this resource as changed in the subworld, as if it had been mutably
+    /// dereferenced.
+    pub fn set_changed(&mut self) {
+        self.ticks.set_changed(self.change_tick);
+    }
+
+    /// Returns a mutable reference to the resource without flagging it as changed. Systems that
+    /// check [`is_changed`](Self::is_changed) will not see writes made through this reference.
+    pub fn bypass_change_detection(&mut self) -> &mut T {
+        &mut self.value
+    }
 }
 
 impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParam for OtherResMut<'a, W, T> {
@@ -40,7 +61,13 @@ impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParam for
 }
 
 pub struct OtherResMutState<W: DerefMut<Target = World> + Component, T> {
-    component_id: ComponentId,
+    // NOTE: the subworld might not exist yet when the system is initialized, in which case the id
+    // of the resource is looked up the first time it is fetched. The id is only valid for the world
+    // it was looked up in, so it's looked up again whenever the subworld is replaced.
+    component_id: Option<(WorldId, ComponentId)>,
+    outer_component_id: ComponentId,
+    resource_ids: Vec<ComponentId>,
+    ticks: SubWorldTicks,
     marker: PhantomData<(T, W)>,
 }
 
@@ -50,8 +77,10 @@ unsafe impl<W: DerefMut<Target = World> + Component, T: Component> SystemParamSt
     fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
         let outer_component_id = world.initialize_resource::<Other<W, T>>();
         let world_id = world.initialize_resource::<W>();
-        let mut world = unsafe{ world.get_resource_unchecked_mut::<W>().expect("Couldn't find world!") };
-        let component_id = world.initialize_resource::<T>();
+        let component_id = get_subworld_mut::<W>(world)
+            .map(|world| (world.id(), world.initialize_resource::<T>()));
+        let nested_reads = nested_reads::<W>(world);
+        let resource_ids = subworld_resource_ids::<W>(world);
         let combined_access = system_state.component_access_set.combined_access_mut();
         if combined_access.has_write(outer_component_id) || combined_access.has_read(outer_component_id) || combined_access.has_read(world_id) || combined_access.has_write(world_id){
             panic!(
@@ -59,16 +88,59 @@ unsafe impl<W: DerefMut<Target = World> + Component, T: Component> SystemParamSt
                 std::any::type_name::<W>(), std::any::type_name::<T>(), system_state.name);
         }
         combined_access.add_write(outer_component_id);
+        for component_id in nested_reads{
+            if combined_access.has_write(component_id){
+                panic!(
+                    "ResMut<{}, {}> in system {} conflicts with mutable access to a world it is nested in. Allowing this would break Rust's mutability rules. Consider removing the duplicate access.",
+                    std::any::type_name::<W>(), std::any::type_name::<T>(), system_state.name);
+            }
+            combined_access.add_read(component_id);
+        }
 
         Self {
             component_id,
+            outer_component_id,
+            resource_ids,
+            ticks: SubWorldTicks::default(),
             marker: PhantomData,
         }
     }
 
+    fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+        add_resource_archetype_access(&self.resource_ids, &[self.outer_component_id], archetype, system_state);
+    }
+
     fn default_config() {}
 }
 
+impl<W: DerefMut<Target = World> + Component, T: Component> OtherResMutState<W, T> {
+    /// Fetches the resource from the subworld, returning `None` if it does not exist.
+    ///
+    /// # Safety
+    /// The access of this state has to be registered with the system fetching the resource.
+    pub(crate) unsafe fn get_other_res_mut<'a>(&'a mut self, world: &'a W) -> Option<OtherResMut<'a, W, T>> {
+        let component_id = match self.component_id {
+            Some((world_id, component_id)) if world_id == world.id() => component_id,
+            _ => {
+                let component_id = world.components().get_resource_id(TypeId::of::<T>())?;
+                self.component_id = Some((world.id(), component_id));
+                component_id
+            }
+        };
+        let value = world.get_resource_unchecked_mut_with_id::<T>(component_id)?;
+        let (last_change_tick, change_tick) = self.ticks.advance(world);
+
+        let (value, ticks) = value.value_and_ticks();
+        Some(OtherResMut {
+            value,
+            ticks,
+            last_change_tick,
+            change_tick,
+            world,
+        })
+    }
+}
+
 impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParamFetch<'a> for OtherResMutState<W, T> {
     type Item = OtherResMut<'a, W, T>;
 
@@ -77,28 +149,81 @@ impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParamFetch
         state: &'a mut Self,
         system_state: &'a SystemState,
         world: &'a World,
-        change_tick: u32,
+        _change_tick: u32,
     ) -> Self::Item {
-        let mut world = world.get_resource_unchecked_mut::<W>().expect("Couldn't find world!");
-        let mut world = world.value_and_ticks().0;
-        let value = world
-            .get_resource_unchecked_mut_with_id::<T>(state.component_id)
+        let world = get_subworld::<W>(world).expect("Couldn't find world!");
+        state
+            .get_other_res_mut(world)
             .unwrap_or_else(|| {
                 panic!(
                     "Resource requested by {} does not exist: {}",
                     system_state.name,
                     std::any::type_name::<T>()
                 )
-            });
+            })
+    }
+}
 
-        let (value, ticks) = value.value_and_ticks();
-        OtherResMut {
-            value,
-            ticks,
-            last_change_tick: system_state.last_change_tick,
-            change_tick,
-            world,
-        }
+/// Unique borrow of a resource in the subworld `W` that might not exist.
+///
+/// Holds `None` if either the subworld or the resource is missing, instead of panicking like
+/// [`OtherResMut`] does.
+// NOTE: SystemParam can't be implemented for Option<OtherResMut> outside of bevy, so this wraps it
+pub struct OptionOtherResMut<'w, W: DerefMut<Target = World> + Component, T: Component>(Option<OtherResMut<'w, W, T>>);
+
+impl<'w, W: DerefMut<Target = World> + Component, T: Component> OptionOtherResMut<'w, W, T> {
+    pub fn into_inner(self) -> Option<OtherResMut<'w, W, T>> {
+        self.0
+    }
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParam for OptionOtherResMut<'a, W, T> {
+    type Fetch = OptionOtherResMutState<W, T>;
+}
+
+pub struct OptionOtherResMutState<W: DerefMut<Target = World> + Component, T>(OtherResMutState<W, T>);
+
+unsafe impl<W: DerefMut<Target = World> + Component, T: Component> SystemParamState for OptionOtherResMutState<W, T> {
+    type Config = ();
+
+    fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
+        Self(OtherResMutState::init(world, system_state, ()))
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+        self.0.new_archetype(archetype, system_state);
+    }
+
+    fn default_config() {}
+}
+
+impl<'a, W: DerefMut<Target = World> + Component, T: Component> SystemParamFetch<'a> for OptionOtherResMutState<W, T> {
+    type Item = OptionOtherResMut<'a, W, T>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'a mut Self,
+        _system_state: &'a SystemState,
+        world: &'a World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        let state = &mut state.0;
+        OptionOtherResMut(get_subworld::<W>(world)
+            .and_then(|world| state.get_other_res_mut(world)))
+    }
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, T: Component> Deref for OptionOtherResMut<'w, W, T> {
+    type Target = Option<OtherResMut<'w, W, T>>;
+
+    fn deref(&self) -> &<Self as std::ops::Deref>::Target {
+        &self.0
+    }
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, T: Component> DerefMut for OptionOtherResMut<'w, W, T> {
+    fn deref_mut(&mut self) -> &mut <Self as std::ops::Deref>::Target {
+        &mut self.0
     }
 }
 
@@ -113,6 +238,7 @@ impl<'w, W: DerefMut<Target = World> + Component, T: Component> Deref for OtherR
 
 impl<'w, W: DerefMut<Target = World> + Component, T: Component> DerefMut for OtherResMut<'w, W, T> {    
     fn deref_mut(&mut self) -> &mut <Self as std::ops::Deref>::Target { 
+        self.ticks.set_changed(self.change_tick);
         &mut self.value
     }
 }
\ No newline at end of file
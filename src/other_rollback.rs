@@ -0,0 +1,179 @@
+use crate::nested::get_subworld;
+use crate::nested::get_subworld_mut;
+use crate::other_snapshot::copy_world;
+use crate::other_snapshot::snapshot;
+use crate::other_snapshot::ReflectResource;
+use crate::other_snapshot::WorldSnapshot;
+use bevy::app::AppBuilder;
+use bevy::app::CoreStage;
+use bevy::app::Plugin;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::EntityMap;
+use bevy::ecs::entity::MapEntitiesError;
+use bevy::ecs::schedule::ExclusiveSystemDescriptorCoercion;
+use bevy::ecs::system::IntoExclusiveSystem;
+use bevy::ecs::world::Mut;
+use bevy::ecs::world::World;
+use bevy::reflect::TypeRegistry;
+use bevy::reflect::TypeRegistryArc;
+use std::collections::VecDeque;
+use core::marker::PhantomData;
+use core::ops::DerefMut;
+
+/// Keeps the last few [`snapshot`]s of the subworld `W` so it can be rolled back and re-simulated.
+pub struct SubworldRollback<W: DerefMut<Target = World> + Component>{
+    capacity: usize,
+    tick: u32,
+    snapshots: VecDeque<(u32, WorldSnapshot)>,
+    w: PhantomData<W>,
+}
+
+impl<W: DerefMut<Target = World> + Component> SubworldRollback<W>{
+    /// Creates a rollback buffer that keeps at most `capacity` snapshots, dropping the oldest first.
+    pub fn new(capacity: usize) -> Self{
+        Self{
+            capacity,
+            tick: 0,
+            snapshots: VecDeque::with_capacity(capacity),
+            w: PhantomData,
+        }
+    }
+
+    /// The tick the next snapshot will be recorded as.
+    pub fn tick(&self) -> u32{
+        self.tick
+    }
+
+    /// The ticks that can currently be restored, oldest first.
+    pub fn recorded_ticks(&self) -> impl Iterator<Item = u32> + '_{
+        self.snapshots.iter().map(|(tick, _)| *tick)
+    }
+
+    /// Takes a snapshot of `world` and records it as the current tick, then advances the tick.
+    pub fn record(&mut self, world: &World, registry: &TypeRegistry){
+        if self.capacity == 0{
+            return;
+        }
+        if self.snapshots.len() == self.capacity{
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back((self.tick, snapshot(world, registry)));
+        self.tick = self.tick.wrapping_add(1);
+    }
+
+    /// Restores `world` in place to the state recorded at `tick`, or returns `None` if `tick` is
+    /// not recorded.
+    ///
+    /// Snapshots newer than `tick` are dropped and recording continues right after `tick`, so
+    /// re-simulated frames replace them.
+    ///
+    /// The entities and reflectable resources of `world` are replaced, but the world itself is
+    /// kept. Its id and component ids stay the same and archetypes are only ever added, so every
+    /// [`OtherQueryState`](crate::other_query_state::OtherQueryState) keeps working after a restore.
+    ///
+    /// Entities are respawned, so they generally get new ids. Any [`Entity`](bevy::ecs::entity::Entity)
+    /// stored outside of the subworld goes stale and has to be looked up in
+    /// [`Restored::entity_map`]. Components that don't reflect
+    /// [`Component`](bevy::ecs::reflect::ReflectComponent) were never recorded and are lost, see
+    /// [`Restored::uncloneable`]. References the restored entities couldn't be mapped for are listed
+    /// in [`Restored::unmapped`].
+    pub fn restore(&mut self, tick: u32, world: &mut World, registry: &TypeRegistry) -> Option<Restored>{
+        let index = self.snapshots.iter().position(|(recorded, _)| *recorded == tick)?;
+        self.snapshots.truncate(index + 1);
+        let (_, recorded) = self.snapshots.back().unwrap();
+
+        let entities = world
+            .archetypes()
+            .iter()
+            .flat_map(|archetype| archetype.entities().iter().copied())
+            .collect::<Vec<_>>();
+        for entity in entities{
+            world.despawn(entity);
+        }
+        for registration in registry.iter(){
+            if let Some(reflect_resource) = registration.data::<ReflectResource>(){
+                reflect_resource.remove_resource(world);
+            }
+        }
+        let (restored, mut uncloneable, unmapped) = copy_world(&recorded.world, world, registry);
+        for name in recorded.uncloneable.iter(){
+            if !uncloneable.contains(name){
+                uncloneable.push(name.clone());
+            }
+        }
+
+        let mut entity_map = EntityMap::default();
+        for entity in recorded.entity_map.keys(){
+            let copy = recorded.entity_map.get(entity).unwrap();
+            entity_map.insert(entity, restored.get(copy).unwrap());
+        }
+        self.tick = tick.wrapping_add(1);
+        Some(Restored{
+            entity_map,
+            uncloneable,
+            unmapped,
+        })
+    }
+}
+
+/// The outcome of [`SubworldRollback::restore`].
+pub struct Restored{
+    /// Maps the entities that existed when the snapshot was taken to their restored copies.
+    pub entity_map: EntityMap,
+    /// The names of the component and resource types that couldn't be recorded or restored, since
+    /// they don't reflect [`Component`](bevy::ecs::reflect::ReflectComponent) or
+    /// [`Resource`](ReflectResource). Components of these types are gone after the restore.
+    pub uncloneable: Vec<String>,
+    /// Entity references that couldn't be mapped to the restored entities, see
+    /// [`WorldSnapshot::unmapped`].
+    pub unmapped: Vec<MapEntitiesError>,
+}
+
+/// Records a snapshot of the subworld `W` into its [`SubworldRollback<W>`].
+pub fn record_subworld<W: DerefMut<Target = World> + Component>(world: &mut World){
+    let registry = world
+        .get_resource::<TypeRegistryArc>()
+        .expect("Recording a subworld needs a TypeRegistryArc resource")
+        .clone();
+    let registry = registry.read();
+    world.resource_scope(|world, mut rollback: Mut<SubworldRollback<W>>| {
+        let other = get_subworld::<W>(world).expect("Couldn't find world!");
+        rollback.record(other, &registry);
+    });
+}
+
+/// Restores the subworld `W` to `tick` using its [`SubworldRollback<W>`]. See
+/// [`SubworldRollback::restore`].
+pub fn restore_subworld<W: DerefMut<Target = World> + Component>(world: &mut World, tick: u32) -> Option<Restored>{
+    let registry = world
+        .get_resource::<TypeRegistryArc>()
+        .expect("Restoring a subworld needs a TypeRegistryArc resource")
+        .clone();
+    let registry = registry.read();
+    world.resource_scope(|world, mut rollback: Mut<SubworldRollback<W>>| {
+        let other = get_subworld_mut::<W>(world).expect("Couldn't find world!");
+        rollback.restore(tick, other, &registry)
+    })
+}
+
+/// Records a snapshot of the subworld `W` at the end of every frame, keeping the last `capacity` of them.
+pub struct SubworldRollbackPlugin<W: DerefMut<Target = World> + Component>{
+    capacity: usize,
+    w: PhantomData<W>,
+}
+
+impl<W: DerefMut<Target = World> + Component> SubworldRollbackPlugin<W>{
+    pub fn new(capacity: usize) -> Self{
+        Self{
+            capacity,
+            w: PhantomData,
+        }
+    }
+}
+
+impl<W: DerefMut<Target = World> + Component> Plugin for SubworldRollbackPlugin<W>{
+    fn build(&self, app: &mut AppBuilder){
+        app.insert_resource(SubworldRollback::<W>::new(self.capacity))
+            .add_system_to_stage(CoreStage::Last, record_subworld::<W>.exclusive_system().at_end());
+    }
+}
\ No newline at end of file
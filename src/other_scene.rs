@@ -0,0 +1,81 @@
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::EntityMap;
+use bevy::ecs::world::World;
+use bevy::reflect::TypeRegistryArc;
+use bevy::scene::serde::SceneDeserializer;
+use bevy::scene::DynamicScene;
+use bevy::scene::SceneSpawnError;
+use serde::de::DeserializeSeed;
+use core::fmt;
+use core::ops::DerefMut;
+use std::path::Path;
+
+/// Error returned when a subworld can't be loaded from a scene.
+#[derive(Debug)]
+pub enum SubworldSceneError{
+    Io(std::io::Error),
+    Ron(ron::Error),
+    Spawn(SceneSpawnError),
+}
+
+impl fmt::Display for SubworldSceneError{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        match self{
+            SubworldSceneError::Io(error) => write!(f, "couldn't read subworld scene: {}", error),
+            SubworldSceneError::Ron(error) => write!(f, "couldn't parse subworld scene: {}", error),
+            SubworldSceneError::Spawn(error) => write!(f, "couldn't spawn subworld scene: {:?}", error),
+        }
+    }
+}
+
+impl std::error::Error for SubworldSceneError{}
+
+/// Saves the content of a subworld as a [`DynamicScene`].
+///
+/// Only entities and the components registered in the type registry end up in the scene, since
+/// that is all a [`DynamicScene`] can hold. Resources of the subworld are not saved.
+pub trait SubworldSceneExt{
+    fn save_scene(&self, type_registry: &TypeRegistryArc) -> DynamicScene;
+
+    /// Like [`save_scene`](Self::save_scene), but serializes the scene to RON right away.
+    fn save_scene_ron(&self, type_registry: &TypeRegistryArc) -> Result<String, ron::Error>{
+        self.save_scene(type_registry).serialize_ron(type_registry)
+    }
+}
+
+impl<W: DerefMut<Target = World> + Component> SubworldSceneExt for W{
+    fn save_scene(&self, type_registry: &TypeRegistryArc) -> DynamicScene{
+        DynamicScene::from_world(self, type_registry)
+    }
+}
+
+/// Builds a fresh subworld from `scene`.
+pub fn load_scene<W: DerefMut<Target = World> + Component + From<World>>(scene: &DynamicScene, type_registry: &TypeRegistryArc) -> Result<W, SubworldSceneError>{
+    let mut world = World::default();
+    world.insert_resource(type_registry.clone());
+    scene
+        .write_to_world(&mut world, &mut EntityMap::default())
+        .map_err(SubworldSceneError::Spawn)?;
+    world.remove_resource::<TypeRegistryArc>();
+    Ok(W::from(world))
+}
+
+/// Builds a fresh subworld from a scene serialized as RON.
+pub fn load_scene_ron<W: DerefMut<Target = World> + Component + From<World>>(ron: &str, type_registry: &TypeRegistryArc) -> Result<W, SubworldSceneError>{
+    let scene = {
+        let registry = type_registry.read();
+        let mut deserializer = ron::de::Deserializer::from_str(ron).map_err(SubworldSceneError::Ron)?;
+        SceneDeserializer{
+            type_registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .map_err(SubworldSceneError::Ron)?
+    };
+    load_scene(&scene, type_registry)
+}
+
+/// Builds a fresh subworld from a `.scn.ron` file.
+pub fn load_scene_file<W: DerefMut<Target = World> + Component + From<World>>(path: impl AsRef<Path>, type_registry: &TypeRegistryArc) -> Result<W, SubworldSceneError>{
+    let ron = std::fs::read_to_string(path).map_err(SubworldSceneError::Io)?;
+    load_scene_ron(&ron, type_registry)
+}
\ No newline at end of file
@@ -0,0 +1,109 @@
+use crate::nested::get_subworld_mut;
+use bevy::app::AppBuilder;
+use bevy::ecs::schedule::Schedule;
+use bevy::ecs::schedule::Stage;
+use bevy::ecs::schedule::StageLabel;
+use bevy::ecs::schedule::SystemDescriptor;
+use bevy::ecs::schedule::SystemStage;
+use bevy::ecs::system::IntoExclusiveSystem;
+use bevy::ecs::world::Mut;
+use bevy::ecs::world::World;
+use bevy::ecs::component::Component;
+use core::marker::PhantomData;
+use core::ops::DerefMut;
+
+/// The names of the default subworld stages.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, StageLabel)]
+pub enum OtherStage {
+    /// Runs before [`OtherStage::Update`] every time the subworld schedule runs.
+    PreUpdate,
+    /// Systems added with [`OtherScheduleAppExt::add_subworld_system`] end up here.
+    Update,
+    /// Runs after [`OtherStage::Update`] every time the subworld schedule runs.
+    PostUpdate,
+}
+
+/// A [`Schedule`] that is run directly against the inner world of `W`.
+///
+/// Systems in this schedule see the subworld as their `World`, so ordinary `Query`, `Res` and
+/// `Commands` params all operate on subworld data.
+pub struct OtherSchedule<W: DerefMut<Target = World> + Component> {
+    schedule: Schedule,
+    w: PhantomData<W>,
+}
+
+impl<W: DerefMut<Target = World> + Component> Default for OtherSchedule<W> {
+    fn default() -> Self {
+        Self {
+            schedule: Schedule::default()
+                .with_stage(OtherStage::PreUpdate, SystemStage::parallel())
+                .with_stage_after(OtherStage::PreUpdate, OtherStage::Update, SystemStage::parallel())
+                .with_stage_after(OtherStage::Update, OtherStage::PostUpdate, SystemStage::parallel()),
+            w: PhantomData,
+        }
+    }
+}
+
+impl<W: DerefMut<Target = World> + Component> OtherSchedule<W> {
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    pub fn schedule_mut(&mut self) -> &mut Schedule {
+        &mut self.schedule
+    }
+}
+
+/// Runs the [`OtherSchedule<W>`] against the subworld `W`.
+///
+/// This is added to the app automatically the first time a subworld system or stage is added for `W`.
+pub fn run_other_schedule<W: DerefMut<Target = World> + Component>(world: &mut World) {
+    world.resource_scope(|world, mut schedule: Mut<OtherSchedule<W>>| {
+        let other = get_subworld_mut::<W>(world).expect("Couldn't find world!");
+        schedule.schedule.run(other);
+    });
+}
+
+pub trait OtherScheduleAppExt {
+    fn add_subworld_stage<W: DerefMut<Target = World> + Component, S: Stage>(&mut self, label: impl StageLabel, stage: S) -> &mut Self;
+    fn add_subworld_stage_after<W: DerefMut<Target = World> + Component, S: Stage>(&mut self, target: impl StageLabel, label: impl StageLabel, stage: S) -> &mut Self;
+    fn add_subworld_stage_before<W: DerefMut<Target = World> + Component, S: Stage>(&mut self, target: impl StageLabel, label: impl StageLabel, stage: S) -> &mut Self;
+    fn add_subworld_system<W: DerefMut<Target = World> + Component>(&mut self, system: impl Into<SystemDescriptor>) -> &mut Self;
+    fn add_subworld_system_to_stage<W: DerefMut<Target = World> + Component>(&mut self, stage_label: impl StageLabel, system: impl Into<SystemDescriptor>) -> &mut Self;
+}
+
+impl OtherScheduleAppExt for AppBuilder {
+    fn add_subworld_stage<W: DerefMut<Target = World> + Component, S: Stage>(&mut self, label: impl StageLabel, stage: S) -> &mut Self {
+        other_schedule::<W>(self).schedule.add_stage(label, stage);
+        self
+    }
+
+    fn add_subworld_stage_after<W: DerefMut<Target = World> + Component, S: Stage>(&mut self, target: impl StageLabel, label: impl StageLabel, stage: S) -> &mut Self {
+        other_schedule::<W>(self).schedule.add_stage_after(target, label, stage);
+        self
+    }
+
+    fn add_subworld_stage_before<W: DerefMut<Target = World> + Component, S: Stage>(&mut self, target: impl StageLabel, label: impl StageLabel, stage: S) -> &mut Self {
+        other_schedule::<W>(self).schedule.add_stage_before(target, label, stage);
+        self
+    }
+
+    fn add_subworld_system<W: DerefMut<Target = World> + Component>(&mut self, system: impl Into<SystemDescriptor>) -> &mut Self {
+        self.add_subworld_system_to_stage::<W>(OtherStage::Update, system)
+    }
+
+    fn add_subworld_system_to_stage<W: DerefMut<Target = World> + Component>(&mut self, stage_label: impl StageLabel, system: impl Into<SystemDescriptor>) -> &mut Self {
+        other_schedule::<W>(self).schedule.add_system_to_stage(stage_label, system);
+        self
+    }
+}
+
+fn other_schedule<W: DerefMut<Target = World> + Component>(app: &mut AppBuilder) -> Mut<'_, OtherSchedule<W>> {
+    if !app.world_mut().contains_resource::<OtherSchedule<W>>() {
+        app.insert_resource(OtherSchedule::<W>::default())
+            .add_system(run_other_schedule::<W>.exclusive_system());
+    }
+    app.world_mut()
+        .get_resource_mut::<OtherSchedule<W>>()
+        .unwrap()
+}
\ No newline at end of file
@@ -0,0 +1,152 @@
+use crate::nested::get_subworld;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::EntityMap;
+use bevy::ecs::entity::MapEntitiesError;
+use bevy::ecs::reflect::ReflectComponent;
+use bevy::ecs::reflect::ReflectMapEntities;
+use bevy::ecs::world::FromWorld;
+use bevy::ecs::world::World;
+use bevy::reflect::FromType;
+use bevy::reflect::Reflect;
+use bevy::reflect::TypeRegistry;
+use bevy::reflect::TypeRegistryArc;
+use core::ops::DerefMut;
+
+/// Type data that lets a resource be copied into another world through reflection. Registered with
+/// `#[reflect(Resource)]`, just like [`ReflectComponent`] is for components.
+#[derive(Clone)]
+pub struct ReflectResource{
+    copy_resource: fn(&World, &mut World),
+    remove_resource: fn(&mut World),
+}
+
+impl ReflectResource{
+    /// Copies the resource from `source_world` into `destination_world`, overwriting it if it
+    /// already exists there.
+    pub fn copy_resource(&self, source_world: &World, destination_world: &mut World){
+        (self.copy_resource)(source_world, destination_world);
+    }
+
+    pub fn remove_resource(&self, world: &mut World){
+        (self.remove_resource)(world);
+    }
+}
+
+impl<T: Component + Reflect + FromWorld> FromType<T> for ReflectResource{
+    fn from_type() -> Self{
+        Self{
+            copy_resource: |source_world, destination_world| {
+                if let Some(source_value) = source_world.get_resource::<T>(){
+                    let mut destination_value = T::from_world(destination_world);
+                    destination_value.apply(source_value);
+                    destination_world.insert_resource(destination_value);
+                }
+            },
+            remove_resource: |world| {
+                world.remove_resource::<T>();
+            },
+        }
+    }
+}
+
+/// A copy of a world made with [`snapshot`].
+pub struct WorldSnapshot{
+    /// The copied world.
+    pub world: World,
+    /// Maps every entity of the original world to its copy. Entity ids can't be chosen on spawn,
+    /// so the copies generally have different ids.
+    pub entity_map: EntityMap,
+    /// The names of the component and resource types that were left out of the copy, since they
+    /// don't reflect [`Component`](ReflectComponent) or [`Resource`](ReflectResource).
+    pub uncloneable: Vec<String>,
+    /// Entity references that couldn't be mapped to the copies, since they were already dangling.
+    /// Mapping a component type stops at the first of them, so other references of that type may
+    /// still point at the original entities.
+    pub unmapped: Vec<MapEntitiesError>,
+}
+
+impl WorldSnapshot{
+    /// Wraps the copied world into a subworld, forking the world the snapshot was taken from.
+    pub fn into_subworld<W: DerefMut<Target = World> + Component + From<World>>(self) -> W{
+        W::from(self.world)
+    }
+}
+
+/// Copies every entity of `world` with its reflectable components, and every reflectable resource,
+/// into a new world.
+///
+/// References between entities are mapped to the copies for components that register
+/// [`ReflectMapEntities`].
+pub fn snapshot(world: &World, registry: &TypeRegistry) -> WorldSnapshot{
+    let mut copy = World::default();
+    let (entity_map, uncloneable, unmapped) = copy_world(world, &mut copy, registry);
+    WorldSnapshot{
+        world: copy,
+        entity_map,
+        uncloneable,
+        unmapped,
+    }
+}
+
+/// Copies the entities and resources of `source` into `destination`, returning the map from the
+/// source entities to their copies, the names of the types that couldn't be copied and the entity
+/// references that couldn't be mapped.
+pub(crate) fn copy_world(source: &World, destination: &mut World, registry: &TypeRegistry) -> (EntityMap, Vec<String>, Vec<MapEntitiesError>){
+    let mut entity_map = EntityMap::default();
+    let mut uncloneable = Vec::new();
+
+    for archetype in source.archetypes().iter(){
+        for &entity in archetype.entities(){
+            let copy = destination.spawn().id();
+            entity_map.insert(entity, copy);
+            for component_id in archetype.components(){
+                let info = source.components().get_info(component_id).unwrap();
+                match info
+                    .type_id()
+                    .and_then(|type_id| registry.get(type_id))
+                    .and_then(|registration| registration.data::<ReflectComponent>()){
+                    Some(reflect_component) => reflect_component.copy_component(source, destination, entity, copy),
+                    None => add_uncloneable(&mut uncloneable, info.name()),
+                }
+            }
+        }
+    }
+
+    for component_id in source.archetypes().resource().components(){
+        let info = source.components().get_info(component_id).unwrap();
+        match info
+            .type_id()
+            .and_then(|type_id| registry.get(type_id))
+            .and_then(|registration| registration.data::<ReflectResource>()){
+            Some(reflect_resource) => reflect_resource.copy_resource(source, destination),
+            None => add_uncloneable(&mut uncloneable, info.name()),
+        }
+    }
+
+    let mut unmapped = Vec::new();
+    for registration in registry.iter(){
+        if let Some(map_entities) = registration.data::<ReflectMapEntities>(){
+            // NOTE: every entity is copied, so this only fails for references that were already dangling
+            if let Err(error) = map_entities.map_entities(destination, &entity_map){
+                unmapped.push(error);
+            }
+        }
+    }
+    (entity_map, uncloneable, unmapped)
+}
+
+fn add_uncloneable(uncloneable: &mut Vec<String>, name: &str){
+    if !uncloneable.iter().any(|uncloneable| uncloneable == name){
+        uncloneable.push(name.to_string());
+    }
+}
+
+/// Takes a [`snapshot`] of the subworld `W`, using the [`TypeRegistryArc`] of the app world.
+pub fn snapshot_subworld<W: DerefMut<Target = World> + Component>(world: &World) -> WorldSnapshot{
+    let registry = world
+        .get_resource::<TypeRegistryArc>()
+        .expect("Taking a snapshot needs a TypeRegistryArc resource")
+        .read();
+    let other = get_subworld::<W>(world).expect("Couldn't find world!");
+    snapshot(other, &registry)
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use bevy::ecs::world::World;
+
+/// The change ticks a system param sees in a subworld.
+///
+/// Everything in a subworld is stamped with the subworld's own change tick, so ticks read there
+/// can't be compared against the change tick of the outer `SystemState`. Instead, every fetch
+/// advances the change tick of the subworld and remembers it as the last run of the system.
+#[derive(Default)]
+pub(crate) struct SubWorldTicks{
+    last_change_tick: u32,
+}
+
+impl SubWorldTicks{
+    /// Advances the change tick of the subworld `world`, returning the last and the current change
+    /// tick of the system, in that order.
+    pub(crate) fn advance(&mut self, world: &World) -> (u32, u32){
+        let change_tick = world.increment_change_tick();
+        let last_change_tick = std::mem::replace(&mut self.last_change_tick, change_tick);
+        (last_change_tick, change_tick)
+    }
+}
\ No newline at end of file
@@ -2,7 +2,14 @@ use core::ops::DerefMut;
 use bevy::app::AppBuilder;
 use std::ops::Deref;
 use bevy::app::Plugin;
+use bevy::app::CoreStage;
 use bevy::ecs::prelude::*;
+use bevy::ecs::component::Component;
+use core::marker::PhantomData;
+use crate::other_world_query::register_other_component;
+
+/// Change ticks of the subworld are clamped once this many ticks have passed since the last check.
+const CHECK_TICK_THRESHOLD: u32 = 518_400_000;
 
 pub struct OtherWorld<const N: usize>{
     world: World,
@@ -23,23 +30,165 @@ impl<const N: usize> DerefMut for OtherWorld<N>{
 }
 
 impl<const N: usize> OtherWorld<N>{
-    pub(crate) fn new() -> Self{
+    pub fn new() -> Self{
         Self{
             world: World::default()
         }
     }
 }
 
+impl<const N: usize> Default for OtherWorld<N>{
+    fn default() -> Self{
+        Self::new()
+    }
+}
+
+impl<const N: usize> From<World> for OtherWorld<N>{
+    fn from(world: World) -> Self{
+        Self{
+            world
+        }
+    }
+}
+
+/// Inserts the subworld `W` as a resource and keeps it maintained every frame.
+///
+/// The subworld is created with [`FromWorld`] and then seeded with everything added through the
+/// builder methods, in the order they were added.
+pub struct SubWorldPlugin<W: DerefMut<Target = World> + Component>{
+    setup: Vec<Box<dyn Fn(&mut World) + Send + Sync>>,
+    outer_setup: Vec<Box<dyn Fn(&mut World) + Send + Sync>>,
+    w: PhantomData<W>,
+}
+
+/// [`SubWorldPlugin`] for the built in [`OtherWorld<N>`].
+pub type OtherWorldPlugin<const N: usize> = SubWorldPlugin<OtherWorld<N>>;
+
+impl<W: DerefMut<Target = World> + Component> Default for SubWorldPlugin<W>{
+    fn default() -> Self{
+        Self{
+            setup: Vec::new(),
+            outer_setup: Vec::new(),
+            w: PhantomData,
+        }
+    }
+}
+
+impl<W: DerefMut<Target = World> + Component> SubWorldPlugin<W>{
+    pub fn new() -> Self{
+        Self::default()
+    }
+
+    /// Inserts a clone of `resource` into the subworld when the plugin is built.
+    pub fn insert_resource<T: Component + Clone>(self, resource: T) -> Self{
+        self.with_setup(move |world| world.insert_resource(resource.clone()))
+    }
+
+    /// Spawns an entity with a clone of `bundle` into the subworld when the plugin is built.
+    pub fn spawn_bundle<B: Bundle + Clone>(self, bundle: B) -> Self{
+        self.with_setup(move |world| {
+            world.spawn().insert_bundle(bundle.clone());
+        })
+    }
+
+    /// Runs `setup` against the subworld when the plugin is built.
+    pub fn with_setup(mut self, setup: impl Fn(&mut World) + Send + Sync + 'static) -> Self{
+        self.setup.push(Box::new(setup));
+        self
+    }
+
+    /// Registers the subworld component `T` in the app world when the plugin is built, so that
+    /// custom queries over it can be scheduled precisely. See [`register_other_component`].
+    pub fn register_component<T: Component>(mut self) -> Self{
+        self.outer_setup.push(Box::new(|world: &mut World| {
+            register_other_component::<W, T>(world);
+        }));
+        self
+    }
+}
+
+impl<W: DerefMut<Target = World> + Component + FromWorld> Plugin for SubWorldPlugin<W>{
+    fn build(&self, app: &mut AppBuilder){
+        let mut world = W::from_world(app.world_mut());
+        for setup in self.setup.iter(){
+            setup(&mut *world);
+        }
+        for setup in self.outer_setup.iter(){
+            setup(app.world_mut());
+        }
+        app.insert_resource(world)
+            .add_system_to_stage(CoreStage::Last, maintain_subworld::<W>.system());
+    }
+}
+
+/// Per-frame bookkeeping for the subworld `W`, mirroring what [`App::update`](bevy::app::App::update)
+/// does for the app world.
+///
+/// Reserved entities are flushed and removed component trackers are cleared. Component ticks are
+/// clamped whenever they risk wrapping around.
+pub fn maintain_subworld<W: DerefMut<Target = World> + Component>(mut world: ResMut<W>, mut last_check_tick: Local<u32>){
+    maintain_world(&mut world, &mut last_check_tick);
+}
+
+/// The bookkeeping done by [`maintain_subworld`], for a world that isn't stored as a resource.
+pub fn maintain_world(world: &mut World, last_check_tick: &mut u32){
+    world.flush();
+    world.clear_trackers();
+    let change_tick = world.change_tick();
+    if change_tick.wrapping_sub(*last_check_tick) > CHECK_TICK_THRESHOLD{
+        world.check_change_ticks();
+        *last_check_tick = change_tick;
+    }
+}
+
 #[cfg(test)]
 mod tests{
     use core::ops::DerefMut;
     use core::ops::Deref;
     use crate::other_query::OtherQuery;
+    use crate::other_query::OptionOtherQuery;
+    use crate::other_query::OtherQueryManyError;
     use crate::other_res::OtherRes;
+    use crate::other_res::OptionOtherRes;
     use crate::other_res_mut::OtherResMut;
+    use crate::other_non_send::OtherNonSend;
+    use crate::other_non_send_mut::OtherNonSendMut;
+    use crate::other_commands::OtherCommands;
+    use crate::other_world::OtherWorld;
+    use crate::other_world::OtherWorldPlugin;
+    use crate::other_schedule::OtherScheduleAppExt;
+    use crate::other_query_set::OtherQuerySet;
+    use crate::other_events::OtherEventReader;
+    use crate::other_events::OtherEventWriter;
+    use crate::other_events::OtherEventsAppExt;
+    use crate::other_entity::transfer_entity;
+    use crate::other_entity::retrieve_entity;
+    use crate::other_entity::retrieve_entities;
+    use crate::other_entity::MoveEntitiesError;
+    use crate::other_snapshot::snapshot_subworld;
+    use crate::other_snapshot::ReflectResource;
+    use crate::other_rollback::SubworldRollbackPlugin;
+    use crate::other_rollback::restore_subworld;
+    use crate::other_scene::SubworldSceneExt;
+    use crate::other_scene::load_scene_ron;
+    use crate::other_worlds::SubWorlds;
+    use crate::other_worlds::SubWorldsPlugin;
+    use crate::other_worlds::OtherQueries;
+    use crate::per_entity_other_query::PerEntityOtherQuery;
+    use crate::other_world_query::Derived;
+    use crate::nested::Nested;
+    use crate::nested::NestedSubWorldAppExt;
+    use bevy::ecs::reflect::ReflectComponent;
+    use bevy::reflect::TypeRegistryArc;
     use bevy::ecs::world::World;
+    use bevy::ecs::component::ComponentDescriptor;
+    use bevy::ecs::component::StorageType;
+    use bevy::tasks::TaskPool;
+    use bevy::ecs::system::System;
+    use bevy::tasks::ParallelIterator;
     use bevy::winit::WinitConfig;
     use bevy::app::App;
+    use bevy::app::Events;
     use bevy::prelude::*;
 
     #[test]
@@ -120,6 +269,793 @@ mod tests{
         thingy.make_ascii_uppercase();
     }
 
+    #[test]
+    fn other_commands(){
+        let mut app = App::build();
+            app.insert_resource(SubWorld{world: World::default()})
+            .add_system(other_commands_spawn.system());
+        let mut app = app.app;
+        app.update();
+        app.update();
+
+        let mut sub_world = app.world.get_resource_mut::<SubWorld>().unwrap();
+        let count = sub_world.query::<(&u32, &i32)>().iter(&sub_world).count();
+        assert_eq!(count, 2);
+        assert_eq!(sub_world.get_resource::<String>().unwrap(), "Hello!");
+    }
+
+    fn other_commands_spawn(mut commands: OtherCommands<SubWorld>){
+        commands
+            .spawn()
+            .insert(20u32)
+            .insert(-21i32);
+        commands.insert_resource(String::from("Hello!"));
+    }
+
+    #[test]
+    fn other_world_plugin(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<0>::new()
+                .insert_resource(String::from("Hello!"))
+                .spawn_bundle((20u32, -21i32))
+                .spawn_bundle((20u32,)))
+            .add_system(other_world_plugin_query.system());
+        let mut app = app.app;
+        app.update();
+        app.update();
+    }
+
+    fn other_world_plugin_query(
+        q: OtherQuery<OtherWorld<0>, (&u32, &i32)>,
+        with: OtherQuery<OtherWorld<0>, &u32, With<i32>>,
+        without: OtherQuery<OtherWorld<0>, &u32, Or<(Without<i32>, Added<bool>)>>,
+        optional: OtherQuery<OtherWorld<0>, (Entity, (&u32, Option<&i32>), ChangeTrackers<u32>)>,
+        res: OtherRes<OtherWorld<0>, String>){
+        assert_eq!(q.iter().count(), 1);
+        assert_eq!(optional.iter().filter(|(_, (_, i), _)| i.is_some()).count(), 1);
+        assert_eq!(optional.iter().count(), 2);
+        assert_eq!(with.iter().count(), 1);
+        assert_eq!(without.iter().count(), 1);
+        assert_eq!(*res, "Hello!");
+    }
+
+    #[test]
+    #[should_panic(expected = "conflicts with a previous system parameter")]
+    fn or_filters_dont_make_queries_disjoint(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<33>::new()
+                .spawn_bundle((1u32, -1i32, true)))
+            .add_system(or_overlapping_system.system());
+        let mut app = app.app;
+        app.update();
+    }
+
+    fn or_overlapping_system(_or: OtherQuery<OtherWorld<33>, &mut u32, Or<(With<i32>, With<bool>)>>, _without: OtherQuery<OtherWorld<33>, &mut u32, Without<i32>>){}
+
+    #[test]
+    fn other_schedule(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<1>::new()
+                .spawn_bundle((0u32,)))
+            .add_subworld_system::<OtherWorld<1>>(other_schedule_step.system());
+        let mut app = app.app;
+        app.update();
+        app.update();
+
+        let mut sub_world = app.world.get_resource_mut::<OtherWorld<1>>().unwrap();
+        let steps = sub_world.query::<&u32>().iter(&sub_world).copied().collect::<Vec<u32>>();
+        assert_eq!(steps, vec![2]);
+    }
+
+    fn other_schedule_step(mut q: Query<&mut u32>){
+        for mut step in q.iter_mut(){
+            *step += 1;
+        }
+    }
+
+    #[test]
+    fn other_query_change_detection(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<2>::new()
+                .spawn_bundle((0u32,))
+                .spawn_bundle((0u32,)))
+            .insert_resource(Vec::<usize>::new())
+            .add_system(other_query_changed.system());
+        let mut app = app.app;
+        app.update();
+        app.update();
+
+        assert_eq!(*app.world.get_resource::<Vec<usize>>().unwrap(), vec![2, 0]);
+    }
+
+    fn other_query_changed(q: OtherQuery<OtherWorld<2>, &u32, Changed<u32>>, mut counts: ResMut<Vec<usize>>){
+        counts.push(q.iter().count());
+    }
+
+    #[test]
+    fn other_query_new_archetype(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<3>::new())
+            .insert_resource(Vec::<usize>::new())
+            .add_system(other_query_new_archetype_spawn.system())
+            .add_system(other_query_new_archetype_count.system());
+        let mut app = app.app;
+        app.update();
+        app.update();
+
+        assert_eq!(*app.world.get_resource::<Vec<usize>>().unwrap(), vec![0, 1]);
+    }
+
+    fn other_query_new_archetype_spawn(mut commands: OtherCommands<OtherWorld<3>>, mut spawned: Local<bool>){
+        if !*spawned{
+            commands.spawn().insert(20u32).insert(true);
+            *spawned = true;
+        }
+    }
+
+    fn other_query_new_archetype_count(q: OtherQuery<OtherWorld<3>, (&u32, &bool)>, mut counts: ResMut<Vec<usize>>){
+        counts.push(q.iter().count());
+    }
+
+    #[test]
+    fn other_query_new_archetype_counted_first(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<30>::new())
+            .insert_resource(Vec::<usize>::new());
+        let mut world = app.app.world;
+        let mut count = other_query_new_archetype_count_first.system();
+        let mut spawn = other_query_new_archetype_spawn_second.system();
+        count.initialize(&mut world);
+        spawn.initialize(&mut world);
+        count.run((), &mut world);
+        spawn.run((), &mut world);
+        spawn.apply_buffers(&mut world);
+        count.run((), &mut world);
+
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![0, 1]);
+    }
+
+    fn other_query_new_archetype_count_first(q: OtherQuery<OtherWorld<30>, (&u32, &bool)>, mut counts: ResMut<Vec<usize>>){
+        counts.push(q.iter().count());
+    }
+
+    fn other_query_new_archetype_spawn_second(mut commands: OtherCommands<OtherWorld<30>>){
+        commands.spawn().insert(20u32).insert(true);
+    }
+
+    #[test]
+    fn other_query_set(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<4>::new()
+                .spawn_bundle((10u32, -10i32))
+                .spawn_bundle((10u32,)))
+            .add_system(other_query_set_system.system());
+        let mut app = app.app;
+        app.update();
+        app.update();
+    }
+
+    fn other_query_set_system(mut set: OtherQuerySet<(OtherQuery<OtherWorld<4>, &mut u32>, OtherQuery<OtherWorld<4>, (&u32, &i32)>)>){
+        for mut u in set.q0_mut().iter_mut(){
+            *u += 1;
+        }
+        for (u, i) in set.q1().iter(){
+            assert!(*u > 10);
+            assert_eq!(*i, -10);
+        }
+    }
+
+    #[test]
+    fn other_events(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<5>::new())
+            .add_other_event::<OtherWorld<5>, u32>()
+            .insert_resource(Vec::<u32>::new())
+            .add_system_to_stage(CoreStage::PreUpdate, other_events_send.system())
+            .add_system(other_events_read.system());
+        let mut app = app.app;
+        app.update();
+        app.update();
+
+        assert_eq!(*app.world.get_resource::<Vec<u32>>().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn other_events_without_subworld(){
+        let mut app = App::build();
+            app.add_other_event::<OtherWorld<35>, u32>();
+        let mut app = app.app;
+        app.update();
+        app.world.insert_resource(OtherWorld::<35>::new());
+        app.update();
+
+        assert!(app.world.get_resource::<OtherWorld<35>>().unwrap().contains_resource::<Events<u32>>());
+    }
+
+    fn other_events_send(mut events: OtherEventWriter<OtherWorld<5>, u32>, mut sent: Local<u32>){
+        *sent += 1;
+        events.send(*sent);
+    }
+
+    fn other_events_read(mut events: OtherEventReader<OtherWorld<5>, u32>, mut read: ResMut<Vec<u32>>){
+        read.extend(events.iter().copied());
+    }
+
+    #[test]
+    fn other_res_change_detection(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<6>::new()
+                .insert_resource(0u32))
+            .insert_resource(Vec::<bool>::new())
+            .add_system_to_stage(CoreStage::PreUpdate, other_res_write.system())
+            .add_system(other_res_changed.system());
+        let mut app = app.app;
+        app.update();
+        app.update();
+        app.update();
+
+        assert_eq!(*app.world.get_resource::<Vec<bool>>().unwrap(), vec![true, false, true]);
+    }
+
+    fn other_res_write(mut res: OtherResMut<OtherWorld<6>, u32>, mut frame: Local<u32>){
+        *frame += 1;
+        if *frame == 3{
+            *res += 1;
+        }
+        else{
+            *res.bypass_change_detection() += 1;
+        }
+    }
+
+    fn other_res_changed(res: OtherRes<OtherWorld<6>, u32>, mut changed: ResMut<Vec<bool>>){
+        changed.push(res.is_changed());
+    }
+
+    #[test]
+    fn optional_params(){
+        let mut app = App::build();
+            app.insert_resource(Vec::<(Option<u32>, Option<usize>)>::new())
+            .add_system(optional_params_read.system());
+        let mut app = app.app;
+        app.update();
+
+        let mut other = OtherWorld::<7>::new();
+        other.insert_resource(5u32);
+        other.spawn().insert(1u32);
+        app.world.insert_resource(other);
+        app.update();
+        app.update();
+
+        assert_eq!(*app.world.get_resource::<Vec<(Option<u32>, Option<usize>)>>().unwrap(), vec![(None, None), (Some(5), Some(1)), (Some(5), Some(1))]);
+    }
+
+    fn optional_params_read(res: OptionOtherRes<OtherWorld<7>, u32>, query: OptionOtherQuery<OtherWorld<7>, &u32>, mut read: ResMut<Vec<(Option<u32>, Option<usize>)>>){
+        read.push((res.as_ref().map(|res| **res), query.as_ref().map(|query| query.iter().count())));
+    }
+
+    #[test]
+    fn other_non_send(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<8>::new()
+                .with_setup(|world| world.insert_non_send(NotSend(0, std::ptr::null()))))
+            .insert_resource(Vec::<u32>::new())
+            .add_system_to_stage(CoreStage::PreUpdate, other_non_send_write.system())
+            .add_system(other_non_send_read.system());
+        let mut app = app.app;
+        app.update();
+        app.update();
+
+        assert_eq!(*app.world.get_resource::<Vec<u32>>().unwrap(), vec![1, 2]);
+    }
+
+    struct NotSend(u32, *const ());
+
+    fn other_non_send_write(mut res: OtherNonSendMut<OtherWorld<8>, NotSend>){
+        res.0 += 1;
+    }
+
+    fn other_non_send_read(res: OtherNonSend<OtherWorld<8>, NotSend>, mut read: ResMut<Vec<u32>>){
+        read.push(res.0);
+    }
+
+    #[test]
+    fn registered_components_are_disjoint(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<9>::new()
+                .register_component::<u32>()
+                .register_component::<i32>()
+                .spawn_bundle((1u32, -1i32)))
+            .add_system(disjoint_queries.system());
+        let mut app = app.app;
+        app.update();
+    }
+
+    #[test]
+    fn filters_keep_queries_disjoint(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<10>::new()
+                .spawn_bundle((1u32, -1i32))
+                .spawn_bundle((2u32,)))
+            .add_system(filtered_disjoint_queries.system());
+        let mut app = app.app;
+        app.update();
+    }
+
+    fn disjoint_queries(mut u: OtherQuery<OtherWorld<9>, &mut u32>, mut i: OtherQuery<OtherWorld<9>, &mut i32>){
+        for (mut u, mut i) in u.iter_mut().zip(i.iter_mut()){
+            *u += 1;
+            *i -= 1;
+        }
+    }
+
+    fn filtered_disjoint_queries(_with: OtherQuery<OtherWorld<10>, &mut u32, With<i32>>, _without: OtherQuery<OtherWorld<10>, &mut u32, Without<i32>>){}
+
+    #[test]
+    fn derived_queries_fetch_like_the_wrapped_query(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<31>::new()
+                .spawn_bundle((1u32, -1i32))
+                .spawn_bundle((2u32,)))
+            .insert_resource(Vec::<u32>::new())
+            .add_system(derived_query_system.system());
+        let mut app = app.app;
+        app.update();
+
+        assert_eq!(*app.world.get_resource::<Vec<u32>>().unwrap(), vec![1]);
+    }
+
+    fn derived_query_system(query: OtherQuery<OtherWorld<31>, Derived<(&u32, &i32)>>, mut read: ResMut<Vec<u32>>){
+        read.extend(query.iter().map(|(u, _)| *u));
+    }
+
+    #[test]
+    #[should_panic(expected = "conflicts with a previous system parameter")]
+    fn derived_queries_conflict_with_bevy_queries(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<32>::new()
+                .spawn_bundle((1u32,)))
+            .add_system(derived_conflicting_system.system());
+        let mut app = app.app;
+        app.update();
+    }
+
+    fn derived_conflicting_system(_derived: OtherQuery<OtherWorld<32>, Derived<&mut u32>>, _query: OtherQuery<OtherWorld<32>, &mut u32>){}
+
+    #[test]
+    fn transfer_and_retrieve_entity(){
+        let mut world = World::default();
+        let registry = TypeRegistryArc::default();
+        registry.write().register::<Health>();
+        world.insert_resource(registry);
+        world.insert_resource(OtherWorld::<11>::new());
+
+        let uncloneable = world.spawn().insert(Health(3)).insert(1u32).id();
+        assert!(matches!(transfer_entity::<OtherWorld<11>>(&mut world, uncloneable), Err(MoveEntitiesError::Uncloneable(_, _))));
+        assert_eq!(world.get::<u32>(uncloneable), Some(&1));
+
+        let entity = world.spawn().insert(Health(3)).id();
+        let other_entity = transfer_entity::<OtherWorld<11>>(&mut world, entity).unwrap();
+        assert!(world.get_entity(entity).is_none());
+        assert_eq!(world.get_resource::<OtherWorld<11>>().unwrap().get::<Health>(other_entity), Some(&Health(3)));
+        assert!(matches!(transfer_entity::<OtherWorld<11>>(&mut world, entity), Err(MoveEntitiesError::NoSuchEntity(_))));
+        assert!(matches!(retrieve_entities::<OtherWorld<11>>(&mut world, &[other_entity, other_entity]), Err(MoveEntitiesError::DuplicateEntity(_))));
+        assert!(matches!(transfer_entity::<OtherWorld<34>>(&mut world, uncloneable), Err(MoveEntitiesError::NoSuchWorld)));
+
+        let entity = retrieve_entity::<OtherWorld<11>>(&mut world, other_entity).unwrap();
+        assert!(world.get_resource::<OtherWorld<11>>().unwrap().get_entity(other_entity).is_none());
+        assert_eq!(world.get::<Health>(entity), Some(&Health(3)));
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[test]
+    fn subworld_snapshot(){
+        let mut world = World::default();
+        let registry = TypeRegistryArc::default();
+        registry.write().register::<Health>();
+        registry.write().register::<Score>();
+        world.insert_resource(registry);
+
+        let mut other = OtherWorld::<12>::new();
+        other.insert_resource(Score(10));
+        other.insert_resource(0u32);
+        let entity = other.spawn().insert(Health(3)).insert(1u32).id();
+        world.insert_resource(other);
+
+        let snapshot = snapshot_subworld::<OtherWorld<12>>(&world);
+        assert_eq!(snapshot.uncloneable, vec![std::any::type_name::<u32>().to_string()]);
+        assert!(snapshot.unmapped.is_empty());
+        let copy = snapshot.entity_map.get(entity).unwrap();
+        let fork = snapshot.into_subworld::<OtherWorld<12>>();
+        assert_eq!(fork.get::<Health>(copy), Some(&Health(3)));
+        assert!(fork.get::<u32>(copy).is_none());
+        assert_eq!(fork.get_resource::<Score>(), Some(&Score(10)));
+        assert!(fork.get_resource::<u32>().is_none());
+
+        world.get_resource_mut::<OtherWorld<12>>().unwrap().get_mut::<Health>(entity).unwrap().0 = 0;
+        assert_eq!(fork.get::<Health>(copy), Some(&Health(3)));
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    #[test]
+    fn subworld_rollback(){
+        let registry = TypeRegistryArc::default();
+        registry.write().register::<Health>();
+        let mut app = App::build();
+            app.insert_resource(registry)
+            .add_plugin(OtherWorldPlugin::<13>::new()
+                .spawn_bundle((Health(1),)))
+            .add_plugin(SubworldRollbackPlugin::<OtherWorld<13>>::new(4))
+            .insert_resource(Vec::<u32>::new())
+            .add_system(rollback_heal.system());
+        let mut app = app.app;
+        app.update();
+        app.update();
+        let restored = restore_subworld::<OtherWorld<13>>(&mut app.world, 0).unwrap();
+        assert_eq!(restored.entity_map.keys().count(), 1);
+        assert!(restore_subworld::<OtherWorld<13>>(&mut app.world, 5).is_none());
+        app.update();
+
+        assert_eq!(*app.world.get_resource::<Vec<u32>>().unwrap(), vec![2, 3, 3]);
+    }
+
+    fn rollback_heal(mut query: OtherQuery<OtherWorld<13>, &mut Health>, mut healths: ResMut<Vec<u32>>){
+        for mut health in query.iter_mut(){
+            health.0 += 1;
+            healths.push(health.0);
+        }
+    }
+
+    #[test]
+    fn subworld_scene_round_trip(){
+        let registry = TypeRegistryArc::default();
+        registry.write().register::<u32>();
+        registry.write().register::<Health>();
+
+        let mut other = OtherWorld::<14>::new();
+        other.spawn().insert(Health(3));
+        other.spawn().insert(Health(4));
+        let ron = other.save_scene_ron(&registry).unwrap();
+
+        let mut loaded = load_scene_ron::<OtherWorld<14>>(&ron, &registry).unwrap();
+        let mut healths = loaded.query::<&Health>().iter(&loaded).map(|health| health.0).collect::<Vec<_>>();
+        healths.sort();
+        assert_eq!(healths, vec![3, 4]);
+    }
+
+    #[test]
+    fn other_queries(){
+        let mut app = App::build();
+            app.add_plugin(SubWorldsPlugin::<OtherWorld<15>>::default())
+            .add_system(other_queries_system.system());
+        let mut worlds = app.world_mut().get_resource_mut::<SubWorlds<OtherWorld<15>>>().unwrap();
+        let mut first = OtherWorld::<15>::new();
+        first.spawn().insert(1u32);
+        let mut second = OtherWorld::<15>::new();
+        second.spawn().insert(2u32);
+        let first = worlds.insert(first);
+        let second = worlds.insert(second);
+        let mut app = app.app;
+        app.update();
+        let mut third = OtherWorld::<15>::new();
+        third.spawn().insert(3u32);
+        let third = app.world.get_resource_mut::<SubWorlds<OtherWorld<15>>>().unwrap().insert(third);
+        app.update();
+
+        let mut worlds = app.world.get_resource_mut::<SubWorlds<OtherWorld<15>>>().unwrap();
+        let first = worlds.get_mut(first).unwrap();
+        assert_eq!(first.query::<&u32>().iter(first).copied().collect::<Vec<_>>(), vec![21]);
+        let second = worlds.get_mut(second).unwrap();
+        assert_eq!(second.query::<&u32>().iter(second).copied().collect::<Vec<_>>(), vec![22]);
+        let third = worlds.get_mut(third).unwrap();
+        assert_eq!(third.query::<&u32>().iter(third).copied().collect::<Vec<_>>(), vec![13]);
+    }
+
+    fn other_queries_system(mut queries: OtherQueries<OtherWorld<15>, &mut u32>){
+        for (_, mut value) in queries.iter_mut(){
+            *value += 10;
+        }
+    }
+
+    #[test]
+    fn per_entity_other_query(){
+        let mut app = App::build();
+            app.insert_resource(Vec::<(Entity, u32)>::new())
+            .add_system(per_entity_other_query_system.system());
+        let mut interior = OtherWorld::<16>::new();
+        interior.spawn().insert(1u32);
+        let ship = app.world_mut().spawn().insert(interior).id();
+        let mut interior = OtherWorld::<16>::new();
+        interior.spawn().insert(2u32);
+        let other_ship = app.world_mut().spawn().insert(interior).id();
+        let mut app = app.app;
+        app.update();
+        let mut interior = OtherWorld::<16>::new();
+        interior.spawn().insert(3u32);
+        let new_ship = app.world.spawn().insert(interior).id();
+        app.update();
+
+        let mut read = app.world.get_resource::<Vec<(Entity, u32)>>().unwrap().clone();
+        read.sort();
+        let mut expected = vec![(ship, 1), (ship, 1), (other_ship, 2), (other_ship, 2), (new_ship, 3)];
+        expected.sort();
+        assert_eq!(read, expected);
+    }
+
+    fn per_entity_other_query_system(query: PerEntityOtherQuery<OtherWorld<16>, &u32>, mut read: ResMut<Vec<(Entity, u32)>>){
+        read.extend(query.iter().map(|(entity, value)| (entity, *value)));
+    }
+
+    #[test]
+    fn nested_other_query(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<17>::new()
+                .with_setup(|world| {
+                    let mut inner = OtherWorld::<18>::new();
+                    inner.spawn().insert(1u32);
+                    world.insert_resource(inner);
+                }))
+            .add_nested_subworld::<OtherWorld<17>, OtherWorld<18>>()
+            .insert_resource(Vec::<u32>::new())
+            .add_system(nested_other_query_system.system());
+        let mut app = app.app;
+        app.update();
+        app.update();
+
+        assert_eq!(*app.world.get_resource::<Vec<u32>>().unwrap(), vec![2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "conflicts with a Res<")]
+    fn nested_other_query_conflicts_with_outer_level(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<19>::new()
+                .with_setup(|world| world.insert_resource(OtherWorld::<20>::new())))
+            .add_nested_subworld::<OtherWorld<19>, OtherWorld<20>>()
+            .add_system(nested_conflicting_system.system());
+        let mut app = app.app;
+        app.update();
+    }
+
+    fn nested_other_query_system(mut query: OtherQuery<Nested<OtherWorld<17>, OtherWorld<18>>, &mut u32>, mut read: ResMut<Vec<u32>>){
+        for mut value in query.iter_mut(){
+            *value += 1;
+            read.push(*value);
+        }
+    }
+
+    fn nested_conflicting_system(_query: OtherQuery<Nested<OtherWorld<19>, OtherWorld<20>>, &u32>, _inner: OtherResMut<OtherWorld<19>, OtherWorld<20>>){}
+
+    #[test]
+    #[should_panic(expected = "conflicts with a previous Res<")]
+    fn nested_other_query_conflicts_with_outer_resource(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<27>::new()
+                .with_setup(|world| world.insert_resource(OtherWorld::<28>::new())))
+            .add_nested_subworld::<OtherWorld<27>, OtherWorld<28>>()
+            .add_system(nested_outer_resource_system.system());
+        let mut app = app.app;
+        app.update();
+    }
+
+    fn nested_outer_resource_system(_query: OtherQuery<Nested<OtherWorld<27>, OtherWorld<28>>, &u32>, _outer: ResMut<OtherWorld<27>>){}
+
+    #[test]
+    fn par_for_each_batches_sparse_components(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<21>::new()
+                .with_setup(|world| {
+                    world.register_component(ComponentDescriptor::new::<u64>(StorageType::SparseSet)).unwrap();
+                    for _ in 0..100{
+                        world.spawn().insert(0u64);
+                    }
+                }))
+            .insert_resource(Vec::<usize>::new())
+            .add_system(par_for_each_sparse_system.system());
+        let mut app = app.app;
+        app.update();
+
+        assert_eq!(*app.world.get_resource::<Vec<usize>>().unwrap(), vec![100, 100]);
+        let world = app.world.get_resource::<OtherWorld<21>>().unwrap();
+        assert_eq!(world.entities().len(), 100);
+    }
+
+    fn par_for_each_sparse_system(mut query: OtherQuery<OtherWorld<21>, &mut u64>, pool: Local<TaskPool>, mut counts: ResMut<Vec<usize>>){
+        query.par_for_each_mut(&pool, 8, |mut value| *value += 1);
+        counts.push(query.iter().filter(|value| **value == 1).count());
+        counts.push(query.par_iter_mut(8).count(&pool));
+    }
+
+    #[test]
+    fn disjoint_subworld_archetypes_dont_conflict(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<22>::new()
+                .with_setup(|world| {
+                    world.spawn().insert_bundle((Health(1), Player));
+                    world.spawn().insert_bundle((Health(1), Enemy));
+                }));
+        let mut world = app.app.world;
+        let players = initialized_system(player_health_system.system(), &mut world);
+        let enemies = initialized_system(enemy_health_system.system(), &mut world);
+        let everyone = initialized_system(all_health_system.system(), &mut world);
+
+        assert!(players.archetype_component_access().is_compatible(enemies.archetype_component_access()));
+        assert!(!players.archetype_component_access().is_compatible(everyone.archetype_component_access()));
+        assert!(!enemies.archetype_component_access().is_compatible(everyone.archetype_component_access()));
+    }
+
+    struct Player;
+
+    struct Enemy;
+
+    fn initialized_system<S: System<In = (), Out = ()>>(mut system: S, world: &mut World) -> S{
+        system.initialize(world);
+        for archetype in world.archetypes().iter(){
+            system.new_archetype(archetype);
+        }
+        system
+    }
+
+    fn player_health_system(_query: OtherQuery<OtherWorld<22>, &mut Health, With<Player>>){}
+
+    fn enemy_health_system(_query: OtherQuery<OtherWorld<22>, &mut Health, Without<Player>>){}
+
+    fn all_health_system(_query: OtherQuery<OtherWorld<22>, &Health>){}
+
+    #[test]
+    fn new_subworld_archetypes_are_matched_right_away(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<29>::new()
+                .with_setup(|world| {
+                    world.spawn().insert_bundle((Health(1), Player));
+                    world.spawn().insert_bundle((Health(1), Enemy));
+                }))
+            .insert_resource(Vec::<usize>::new());
+        let mut world = app.app.world;
+        let mut players = initialized_system(count_players_system.system(), &mut world);
+        let enemies = initialized_system(enemy_health_reader_system.system(), &mut world);
+        let archetypes = world.archetypes().len();
+
+        // NOTE: an entity can be both, so these conflict before any such archetype exists
+        assert!(!players.archetype_component_access().is_compatible(enemies.archetype_component_access()));
+        players.run((), &mut world);
+        world.get_resource_mut::<OtherWorld<29>>().unwrap().spawn().insert_bundle((Health(1), Player, Enemy));
+        players.run((), &mut world);
+        players.apply_buffers(&mut world);
+
+        assert_eq!(world.archetypes().len(), archetypes);
+        assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn archetype_only_filters_have_exact_len(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<23>::new()
+                .with_setup(|world| {
+                    world.spawn().insert_bundle((Health(1), Player));
+                    world.spawn().insert_bundle((Health(2), Player));
+                    world.spawn().insert_bundle((Health(3), Enemy));
+                }))
+            .insert_resource(Vec::<usize>::new())
+            .add_system(exact_len_system.system());
+        let mut app = app.app;
+        app.update();
+
+        assert_eq!(*app.world.get_resource::<Vec<usize>>().unwrap(), vec![2, 2, 1, 1, 3, 0]);
+    }
+
+    fn exact_len_system(
+        players: OtherQuery<OtherWorld<23>, &Health, With<Player>>,
+        everyone: OtherQuery<OtherWorld<23>, &Health, Or<(With<Player>, With<Enemy>)>>,
+        nobody: OtherQuery<OtherWorld<23>, &Health, (With<Player>, With<Enemy>)>,
+        mut lens: ResMut<Vec<usize>>){
+        lens.push(players.iter().len());
+        lens.push(players.count());
+        lens.push(players.iter().skip(1).count());
+        lens.push(players.iter().skip(1).len());
+        lens.push(everyone.iter().len());
+        lens.push(nobody.iter().len());
+        assert!(!players.is_empty());
+        assert!(nobody.is_empty());
+    }
+
+    #[test]
+    fn iter_combinations_yields_unordered_triples(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<25>::new()
+                .with_setup(|world| {
+                    for value in 1..5u32{
+                        world.spawn().insert(value);
+                    }
+                }))
+            .insert_resource(Vec::<[u32; 3]>::new())
+            .add_system(triples_system.system());
+        let mut app = app.app;
+        app.update();
+
+        let mut triples = app.world.get_resource::<Vec<[u32; 3]>>().unwrap().clone();
+        triples.sort();
+        assert_eq!(triples, vec![[1, 2, 3], [1, 2, 4], [1, 3, 4], [2, 3, 4]]);
+    }
+
+    fn triples_system(query: OtherQuery<OtherWorld<25>, &u32>, mut triples: ResMut<Vec<[u32; 3]>>){
+        for [a, b, c] in query.iter_combinations::<3>(){
+            let mut triple = [*a, *b, *c];
+            triple.sort();
+            triples.push(triple);
+        }
+    }
+
+    #[test]
+    fn iter_combinations_visits_each_pair_once(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<24>::new()
+                .with_setup(|world| {
+                    for value in 1..5u32{
+                        world.spawn().insert(value);
+                    }
+                }))
+            .insert_resource(Vec::<u32>::new())
+            .add_system(combinations_system.system());
+        let mut app = app.app;
+        app.update();
+
+        let mut values = app.world.get_resource::<Vec<u32>>().unwrap().clone();
+        values.sort();
+        assert_eq!(values, vec![4, 5, 6, 7]);
+    }
+
+    fn combinations_system(mut query: OtherQuery<OtherWorld<24>, &mut u32>, mut values: ResMut<Vec<u32>>){
+        let mut combinations = query.iter_combinations_mut::<2>();
+        let mut pairs = 0;
+        while let Some([mut a, mut b]) = combinations.fetch_next(){
+            assert_ne!(*a, *b);
+            *a += 1;
+            *b += 1;
+            pairs += 1;
+        }
+        assert_eq!(pairs, 6);
+        assert!(query.iter_combinations_mut::<5>().fetch_next().is_none());
+        values.extend(query.iter_mut().map(|value| *value));
+    }
+
+    #[test]
+    fn get_many_mut_rejects_duplicates(){
+        let mut app = App::build();
+            app.add_plugin(OtherWorldPlugin::<26>::new()
+                .with_setup(|world| {
+                    world.spawn().insert_bundle((Health(3), Player));
+                    world.spawn().insert_bundle((Health(10), Enemy));
+                }))
+            .add_system(get_many_system.system());
+        let mut app = app.app;
+        app.update();
+
+        let mut sub_world = app.world.get_resource_mut::<OtherWorld<26>>().unwrap();
+        let mut healths = sub_world.query::<&Health>().iter(&sub_world).map(|health| health.0).collect::<Vec<_>>();
+        healths.sort();
+        assert_eq!(healths, vec![3, 7]);
+    }
+
+    fn get_many_system(
+        mut query: OtherQuery<OtherWorld<26>, &mut Health>,
+        players: OtherQuery<OtherWorld<26>, Entity, With<Player>>,
+        enemies: OtherQuery<OtherWorld<26>, Entity, With<Enemy>>){
+        let attacker = players.single().unwrap();
+        let target = enemies.single().unwrap();
+
+        assert!(matches!(query.get_many_mut([attacker, attacker]), Err(OtherQueryManyError::AliasedMutability(_))));
+        assert!(matches!(query.get_many_mut([attacker, Entity::new(1000)]), Err(OtherQueryManyError::QueryEntityError(_, _))));
+
+        let [attacker, mut target] = query.get_many_mut([attacker, target]).unwrap();
+        target.0 -= attacker.0;
+    }
+
     struct SubWorld{
         world: World,
     }
@@ -0,0 +1,372 @@
+use crate::other::Other;
+use crate::other_query_iter::ArchetypeOnlyFilter;
+use crate::other_query_iter::sealed::Sealed;
+use bevy::ecs::archetype::ArchetypeComponentId;
+use bevy::ecs::component::Component;
+use bevy::ecs::component::ComponentId;
+use bevy::ecs::query::Access;
+use bevy::ecs::query::FetchState;
+use bevy::ecs::query::FilteredAccess;
+use bevy::ecs::query::ReadState;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::AddedState;
+use bevy::ecs::query::Added;
+use bevy::ecs::query::ChangedState;
+use bevy::ecs::query::Changed;
+use bevy::ecs::query::ChangeTrackersState;
+use bevy::ecs::query::ChangeTrackers;
+use bevy::ecs::query::Or;
+use bevy::ecs::query::WithState;
+use bevy::ecs::query::With;
+use bevy::ecs::query::WithoutState;
+use bevy::ecs::query::Without;
+use bevy::ecs::query::WriteState;
+use bevy::ecs::query::WorldQuery;
+use bevy::ecs::world::World;
+use bevy::utils::HashMap;
+use bevy::utils::HashSet;
+use core::any::TypeId;
+use core::marker::PhantomData;
+use core::ops::DerefMut;
+
+/// Stands in for every component of a subworld that was not registered with
+/// [`register_other_component`] in [`derive_outer_component_access`].
+pub struct UnregisteredComponent;
+
+/// Maps the components of the subworld `W` to the ids of their [`Other<W, T>`] markers in the
+/// outer world.
+pub struct OtherComponentIds<W: DerefMut<Target = World> + Component>{
+    ids: HashMap<TypeId, ComponentId>,
+    // NOTE: components some query mapped to the shared marker before they were registered
+    unregistered: HashSet<TypeId>,
+    w: PhantomData<W>,
+}
+
+impl<W: DerefMut<Target = World> + Component> Default for OtherComponentIds<W>{
+    fn default() -> Self{
+        Self{
+            ids: HashMap::default(),
+            unregistered: HashSet::default(),
+            w: PhantomData,
+        }
+    }
+}
+
+impl<W: DerefMut<Target = World> + Component> OtherComponentIds<W>{
+    pub fn get(&self, type_id: TypeId) -> Option<ComponentId>{
+        self.ids.get(&type_id).copied()
+    }
+
+    /// Whether a query over the component was mapped to the [`Other<W, UnregisteredComponent>`]
+    /// marker before the component was registered. Every later query over it records that marker as
+    /// well, so it still conflicts with the earlier one.
+    pub fn was_unregistered(&self, type_id: TypeId) -> bool{
+        self.unregistered.contains(&type_id)
+    }
+}
+
+/// Registers the component `T` of the subworld `W` in the outer world, so that queries using
+/// [`derive_outer_component_access`] only conflict over it with other queries over `T`.
+///
+/// bevy's queries and filters register the components they use themselves. Components that are
+/// never registered all share the [`Other<W, UnregisteredComponent>`] marker, which is always
+/// correct but can keep systems from running in parallel.
+pub fn register_other_component<W: DerefMut<Target = World> + Component, T: Component>(world: &mut World) -> ComponentId{
+    let component_id = outer_component_id::<W, T>(world);
+    world
+        .get_resource_or_insert_with(OtherComponentIds::<W>::default)
+        .ids
+        .insert(TypeId::of::<T>(), component_id);
+    component_id
+}
+
+fn outer_component_id<W: DerefMut<Target = World> + Component, T: Component>(world: &mut World) -> ComponentId{
+    init_component_id::<Other<W, T>>(world)
+}
+
+/// Registers `T` as a component of `world`, returning its id.
+pub(crate) fn init_component_id<T: Component>(world: &mut World) -> ComponentId{
+    <ReadState<T> as FetchState>::init(world);
+    world
+        .components()
+        .get_id(TypeId::of::<T>())
+        .unwrap()
+}
+
+/// Hands out the ids [`init_outer_archetype_component_access`] uses for queries over subworlds.
+#[derive(Default)]
+pub struct OtherArchetypeComponentIds{
+    next: usize,
+}
+
+/// The outer access of every query over the subworld `W` the executor knows about, along with the
+/// id that stands for it.
+pub struct OtherQueryConflicts<W: DerefMut<Target = World> + Component>{
+    queries: Vec<(FilteredAccess<ComponentId>, ArchetypeComponentId)>,
+    w: PhantomData<W>,
+}
+
+impl<W: DerefMut<Target = World> + Component> Default for OtherQueryConflicts<W>{
+    fn default() -> Self{
+        Self{
+            queries: Vec::new(),
+            w: PhantomData,
+        }
+    }
+}
+
+/// Registers a query over the subworld `W` with the outer access `outer_component_access`, returning
+/// the archetype level access the executor should see for it.
+///
+/// The executor only hears about new archetypes of the outer world, so it can't be told about the
+/// archetypes a subworld gains. Instead, every query writes an id of its own and reads the id of
+/// every query registered before it that it conflicts with. That way two queries conflict for the
+/// executor exactly when their outer access does, which takes `With`/`Without` filters into account,
+/// and stays true for every archetype the subworld will ever have.
+pub(crate) fn init_outer_archetype_component_access<W: DerefMut<Target = World> + Component>(world: &mut World, outer_component_access: &FilteredAccess<ComponentId>) -> Access<ArchetypeComponentId>{
+    // NOTE: the outer world keeps handing out ids for its own archetypes, so these may end up
+    // overlapping with them later on. That only ever adds conflicts, it never hides one.
+    let len = world.archetypes().archetype_components_len();
+    let id = {
+        let mut ids = world.get_resource_or_insert_with(OtherArchetypeComponentIds::default);
+        let index = len.max(ids.next);
+        ids.next = index + 1;
+        ArchetypeComponentId::new(index)
+    };
+    let mut access = Access::default();
+    access.add_write(id);
+    let mut conflicts = world.get_resource_or_insert_with(OtherQueryConflicts::<W>::default);
+    for (other_access, other_id) in conflicts.queries.iter(){
+        if !other_access.is_compatible(outer_component_access){
+            access.add_read(*other_id);
+        }
+    }
+    conflicts.queries.push((outer_component_access.clone(), id));
+    access
+}
+
+/// A [`WorldQuery`] over the subworld `W` whose access can be expressed in terms of the outer world.
+///
+/// Every component `T` the query touches in the subworld maps to its own [`Other<W, T>`] marker,
+/// so queries over different components, or that are disjoint through `With`/`Without` filters,
+/// don't conflict. This is implemented for all of bevy's queries and filters. User-defined
+/// [`WorldQuery`] impls can implement it through [`derive_outer_component_access`], or be wrapped
+/// in [`Derived`].
+pub trait OtherWorldQuery<W: DerefMut<Target = World> + Component>: WorldQuery{
+    /// Adds the reads and writes this query makes in the subworld to `outer_access`, as reads and
+    /// writes of the matching [`Other<W, T>`] markers in the outer `world`.
+    fn update_outer_component_access(world: &mut World, outer_access: &mut FilteredAccess<ComponentId>);
+
+    /// Whether this only filters on which components an entity has, so every entity of a matched
+    /// archetype passes it. Only the filters of this crate that implement [`ArchetypeOnlyFilter`]
+    /// set this.
+    const IS_ARCHETYPE_FILTER: bool = false;
+}
+
+/// Computes the outer access of any [`WorldQuery`] `Q` from the access it has in a subworld.
+///
+/// Components registered with [`register_other_component`] map to their own [`Other<W, T>`]
+/// marker, every other component maps to the shared [`Other<W, UnregisteredComponent>`] marker.
+pub fn derive_outer_component_access<W: DerefMut<Target = World> + Component, Q: WorldQuery>(world: &mut World, outer_access: &mut FilteredAccess<ComponentId>){
+    // NOTE: the query is initialized against an empty scratch world, so the access can be
+    // computed before the subworld exists. Only the type ids of its components are kept.
+    let mut scratch = World::new();
+    let state = <Q::State as FetchState>::init(&mut scratch);
+    let mut inner_access = FilteredAccess::default();
+    state.update_component_access(&mut inner_access);
+
+    let unregistered = outer_component_id::<W, UnregisteredComponent>(world);
+    let mut ids = world.get_resource_or_insert_with(OtherComponentIds::<W>::default);
+    let components = scratch.components();
+    // NOTE: FilteredAccess doesn't expose its With/Without sets, so those can't be carried
+    // over and queries that are only disjoint through filters still conflict
+    for index in 0..components.len(){
+        let inner_id = ComponentId::new(index);
+        let type_id = match components.get_info(inner_id).and_then(|info| info.type_id()){
+            Some(type_id) => type_id,
+            None => continue,
+        };
+        let registered = ids.get(type_id);
+        if registered.is_none(){
+            ids.unregistered.insert(type_id);
+        }
+        let shared = if ids.was_unregistered(type_id) { Some(unregistered) } else { None };
+        for outer_id in registered.into_iter().chain(shared){
+            if inner_access.access().has_write(inner_id){
+                outer_access.add_write(outer_id);
+            }
+            else if inner_access.access().has_read(inner_id){
+                outer_access.add_read(outer_id);
+            }
+        }
+    }
+}
+
+/// Adds the reads and writes `outer_access` has of `component_id`, the marker of the component
+/// `type_id`, to the shared [`Other<W, UnregisteredComponent>`] marker as well, if a query over the
+/// component was mapped to it before the component was registered.
+fn add_unregistered_access<W: DerefMut<Target = World> + Component>(world: &mut World, type_id: TypeId, component_id: ComponentId, outer_access: &mut FilteredAccess<ComponentId>){
+    let was_unregistered = world
+        .get_resource::<OtherComponentIds<W>>()
+        .map_or(false, |ids| ids.was_unregistered(type_id));
+    if !was_unregistered{
+        return;
+    }
+    let unregistered = outer_component_id::<W, UnregisteredComponent>(world);
+    if outer_access.access().has_write(component_id){
+        outer_access.add_write(unregistered);
+    }
+    else if outer_access.access().has_read(component_id){
+        outer_access.add_read(unregistered);
+    }
+}
+
+/// Wraps any [`WorldQuery`] `Q` so it can be used in an [`OtherQuery`](crate::other_query::OtherQuery),
+/// fetching exactly what `Q` fetches. Its outer access is computed by
+/// [`derive_outer_component_access`].
+pub struct Derived<Q>(PhantomData<Q>);
+
+impl<Q: WorldQuery> WorldQuery for Derived<Q>{
+    type Fetch = Q::Fetch;
+    type State = Q::State;
+}
+
+impl<W: DerefMut<Target = World> + Component, Q: WorldQuery> OtherWorldQuery<W> for Derived<Q>{
+    fn update_outer_component_access(world: &mut World, outer_access: &mut FilteredAccess<ComponentId>){
+        derive_outer_component_access::<W, Q>(world, outer_access);
+    }
+}
+
+macro_rules! impl_other_world_query_with_state{
+    ($query: ty, $state: ident) => {
+        impl_other_world_query_with_state!($query, $state, false);
+    };
+    ($query: ty, $state: ident, archetype_filter) => {
+        impl_other_world_query_with_state!($query, $state, true);
+
+        impl<T: Component> Sealed for $query {}
+
+        impl<T: Component> ArchetypeOnlyFilter for $query {}
+    };
+    ($query: ty, $state: ident, $is_archetype_filter: expr) => {
+        impl<W: DerefMut<Target = World> + Component, T: Component> OtherWorldQuery<W> for $query{
+            fn update_outer_component_access(world: &mut World, outer_access: &mut FilteredAccess<ComponentId>){
+                // NOTE: registering T makes custom queries over it use the same marker
+                let component_id = register_other_component::<W, T>(world);
+                <$state<Other<W, T>> as FetchState>::init(world).update_component_access(outer_access);
+                add_unregistered_access::<W>(world, TypeId::of::<T>(), component_id, outer_access);
+            }
+
+            const IS_ARCHETYPE_FILTER: bool = $is_archetype_filter;
+        }
+    };
+}
+
+impl_other_world_query_with_state!(&T, ReadState);
+impl_other_world_query_with_state!(&mut T, WriteState);
+impl_other_world_query_with_state!(ChangeTrackers<T>, ChangeTrackersState);
+impl_other_world_query_with_state!(With<T>, WithState, archetype_filter);
+impl_other_world_query_with_state!(Without<T>, WithoutState, archetype_filter);
+impl_other_world_query_with_state!(Changed<T>, ChangedState);
+impl_other_world_query_with_state!(Added<T>, AddedState);
+
+impl<W: DerefMut<Target = World> + Component> OtherWorldQuery<W> for Entity{
+    fn update_outer_component_access(_world: &mut World, _outer_access: &mut FilteredAccess<ComponentId>){}
+}
+
+impl<W: DerefMut<Target = World> + Component> OtherWorldQuery<W> for (){
+    fn update_outer_component_access(_world: &mut World, _outer_access: &mut FilteredAccess<ComponentId>){}
+
+    const IS_ARCHETYPE_FILTER: bool = true;
+}
+
+impl Sealed for () {}
+
+impl ArchetypeOnlyFilter for () {}
+
+impl<W: DerefMut<Target = World> + Component, T: OtherWorldQuery<W>> OtherWorldQuery<W> for Option<T>{
+    fn update_outer_component_access(world: &mut World, outer_access: &mut FilteredAccess<ComponentId>){
+        // NOTE: just like OptionState, only the access is kept, an Option never filters anything out
+        let mut intermediate = outer_access.clone();
+        T::update_outer_component_access(world, &mut intermediate);
+        outer_access.extend_access(&intermediate);
+    }
+}
+
+macro_rules! impl_tuple_other_world_query{
+    ($($name: ident),*) => {
+        impl<W: DerefMut<Target = World> + Component, $($name: OtherWorldQuery<W>,)*> OtherWorldQuery<W> for ($($name,)*){
+            fn update_outer_component_access(world: &mut World, outer_access: &mut FilteredAccess<ComponentId>){
+                $($name::update_outer_component_access(world, outer_access);)*
+            }
+
+            const IS_ARCHETYPE_FILTER: bool = $($name::IS_ARCHETYPE_FILTER &&)* true;
+        }
+
+        impl<$($name: ArchetypeOnlyFilter,)*> Sealed for ($($name,)*) {}
+
+        impl<$($name: ArchetypeOnlyFilter,)*> ArchetypeOnlyFilter for ($($name,)*) {}
+    }
+}
+
+macro_rules! impl_or_other_world_query{
+    ($($name: ident),*) => {
+        impl<W: DerefMut<Target = World> + Component, $($name: OtherWorldQuery<W>,)*> OtherWorldQuery<W> for Or<($($name,)*)>
+        where
+            Or<($($name,)*)>: WorldQuery,
+        {
+            fn update_outer_component_access(world: &mut World, outer_access: &mut FilteredAccess<ComponentId>){
+                // NOTE: an entity only has to pass one of the filters, so only the access is kept.
+                // The With/Without of a single filter would make the query look disjoint from
+                // queries it shares entities with
+                let mut intermediate = outer_access.clone();
+                $($name::update_outer_component_access(world, &mut intermediate);)*
+                outer_access.extend_access(&intermediate);
+            }
+
+            const IS_ARCHETYPE_FILTER: bool = $($name::IS_ARCHETYPE_FILTER &&)* true;
+        }
+
+        impl<$($name: ArchetypeOnlyFilter,)*> Sealed for Or<($($name,)*)>
+        where
+            Or<($($name,)*)>: WorldQuery,
+        {}
+
+        impl<$($name: ArchetypeOnlyFilter,)*> ArchetypeOnlyFilter for Or<($($name,)*)>
+        where
+            Or<($($name,)*)>: WorldQuery,
+        {}
+    }
+}
+
+impl_tuple_other_world_query!(T1);
+impl_tuple_other_world_query!(T1, T2);
+impl_tuple_other_world_query!(T1, T2, T3);
+impl_tuple_other_world_query!(T1, T2, T3, T4);
+impl_tuple_other_world_query!(T1, T2, T3, T4, T5);
+impl_tuple_other_world_query!(T1, T2, T3, T4, T5, T6);
+impl_tuple_other_world_query!(T1, T2, T3, T4, T5, T6, T7);
+impl_tuple_other_world_query!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_tuple_other_world_query!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_tuple_other_world_query!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_tuple_other_world_query!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_tuple_other_world_query!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+impl_tuple_other_world_query!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
+impl_tuple_other_world_query!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
+impl_tuple_other_world_query!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15);
+
+impl_or_other_world_query!(F1);
+impl_or_other_world_query!(F1, F2);
+impl_or_other_world_query!(F1, F2, F3);
+impl_or_other_world_query!(F1, F2, F3, F4);
+impl_or_other_world_query!(F1, F2, F3, F4, F5);
+impl_or_other_world_query!(F1, F2, F3, F4, F5, F6);
+impl_or_other_world_query!(F1, F2, F3, F4, F5, F6, F7);
+impl_or_other_world_query!(F1, F2, F3, F4, F5, F6, F7, F8);
+impl_or_other_world_query!(F1, F2, F3, F4, F5, F6, F7, F8, F9);
+impl_or_other_world_query!(F1, F2, F3, F4, F5, F6, F7, F8, F9, F10);
+impl_or_other_world_query!(F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11);
+impl_or_other_world_query!(F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12);
+impl_or_other_world_query!(F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13);
+impl_or_other_world_query!(F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14);
+impl_or_other_world_query!(F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12, F13, F14, F15);
\ No newline at end of file
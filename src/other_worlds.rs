@@ -0,0 +1,253 @@
+use crate::other_query::OtherQuery;
+use crate::other_query_state::OtherQueryStates;
+use crate::other_world::maintain_world;
+use crate::other_world_query::OtherWorldQuery;
+use bevy::app::AppBuilder;
+use bevy::app::CoreStage;
+use bevy::app::Plugin;
+use bevy::ecs::archetype::Archetype;
+use bevy::ecs::component::Component;
+use bevy::ecs::query::Fetch;
+use bevy::ecs::query::FilterFetch;
+use bevy::ecs::query::ReadOnlyFetch;
+use bevy::ecs::query::WorldQuery;
+use bevy::ecs::system::IntoSystem;
+use bevy::ecs::system::Local;
+use bevy::ecs::system::ResMut;
+use bevy::ecs::system::SystemParam;
+use bevy::ecs::system::SystemParamFetch;
+use bevy::ecs::system::SystemParamState;
+use bevy::ecs::system::SystemState;
+use bevy::ecs::world::World;
+use bevy::utils::HashMap;
+use core::marker::PhantomData;
+use core::ops::DerefMut;
+
+/// Identifies one subworld in a [`SubWorlds<W>`]. Ids are never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SubWorldId(u32);
+
+/// A collection of subworlds of the same type, for when the number of subworlds is only known at
+/// runtime.
+pub struct SubWorlds<W: DerefMut<Target = World> + Component>{
+    worlds: HashMap<SubWorldId, W>,
+    next_id: u32,
+}
+
+impl<W: DerefMut<Target = World> + Component> Default for SubWorlds<W>{
+    fn default() -> Self{
+        Self{
+            worlds: HashMap::default(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<W: DerefMut<Target = World> + Component> SubWorlds<W>{
+    /// Adds `world` to the collection, returning the id it can be looked up by.
+    pub fn insert(&mut self, world: W) -> SubWorldId{
+        let id = SubWorldId(self.next_id);
+        self.next_id += 1;
+        self.worlds.insert(id, world);
+        id
+    }
+
+    pub fn remove(&mut self, id: SubWorldId) -> Option<W>{
+        self.worlds.remove(&id)
+    }
+
+    pub fn get(&self, id: SubWorldId) -> Option<&W>{
+        self.worlds.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: SubWorldId) -> Option<&mut W>{
+        self.worlds.get_mut(&id)
+    }
+
+    pub fn contains(&self, id: SubWorldId) -> bool{
+        self.worlds.contains_key(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SubWorldId, &W)>{
+        self.worlds.iter().map(|(id, world)| (*id, world))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (SubWorldId, &mut W)>{
+        self.worlds.iter_mut().map(|(id, world)| (*id, world))
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = SubWorldId> + '_{
+        self.worlds.keys().copied()
+    }
+
+    pub fn len(&self) -> usize{
+        self.worlds.len()
+    }
+
+    pub fn is_empty(&self) -> bool{
+        self.worlds.is_empty()
+    }
+}
+
+/// Inserts an empty [`SubWorlds<W>`] and maintains every subworld in it each frame, like
+/// [`SubWorldPlugin`](crate::other_world::SubWorldPlugin) does for a single one.
+pub struct SubWorldsPlugin<W: DerefMut<Target = World> + Component>{
+    w: PhantomData<W>,
+}
+
+impl<W: DerefMut<Target = World> + Component> Default for SubWorldsPlugin<W>{
+    fn default() -> Self{
+        Self{
+            w: PhantomData,
+        }
+    }
+}
+
+impl<W: DerefMut<Target = World> + Component> Plugin for SubWorldsPlugin<W>{
+    fn build(&self, app: &mut AppBuilder){
+        app.insert_resource(SubWorlds::<W>::default())
+            .add_system_to_stage(CoreStage::Last, maintain_subworlds::<W>.system());
+    }
+}
+
+/// Runs [`maintain_world`] for every subworld in [`SubWorlds<W>`].
+pub fn maintain_subworlds<W: DerefMut<Target = World> + Component>(mut worlds: ResMut<SubWorlds<W>>, mut last_check_ticks: Local<HashMap<SubWorldId, u32>>){
+    last_check_ticks.retain(|id, _| worlds.contains(*id));
+    for (id, world) in worlds.iter_mut(){
+        maintain_world(world, last_check_ticks.entry(id).or_insert(0));
+    }
+}
+
+/// Runs the same query over every subworld in [`SubWorlds<W>`].
+///
+/// The query is set up for subworlds added since the last run when the system runs, which flags
+/// [`SubWorlds<W>`] as changed. Since that takes mutable access to it, no other system using
+/// [`SubWorlds<W>`] runs alongside.
+pub struct OtherQueries<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static = ()>
+where
+    F::Fetch: FilterFetch,
+{
+    // NOTE: sorted by id, so iteration order doesn't depend on the HashMap
+    queries: Vec<(SubWorldId, OtherQuery<'w, W, Q, F>)>,
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> OtherQueries<'w, W, Q, F>
+where
+    F::Fetch: FilterFetch,
+{
+    /// The query over the subworld `id`.
+    pub fn get(&self, id: SubWorldId) -> Option<&OtherQuery<'w, W, Q, F>>{
+        self.index(id).map(|index| &self.queries[index].1)
+    }
+
+    /// The query over the subworld `id`.
+    pub fn get_mut(&mut self, id: SubWorldId) -> Option<&mut OtherQuery<'w, W, Q, F>>{
+        let index = self.index(id)?;
+        Some(&mut self.queries[index].1)
+    }
+
+    /// The ids of the subworlds this queries, in ascending order.
+    pub fn ids(&self) -> impl Iterator<Item = SubWorldId> + '_{
+        self.queries.iter().map(|(id, _)| *id)
+    }
+
+    /// Iterates over the query results of every subworld. This can only be called for read-only queries
+    pub fn iter(&self) -> impl Iterator<Item = (SubWorldId, <Q::Fetch as Fetch<'_>>::Item)>
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        self.queries
+            .iter()
+            .flat_map(|(id, query)| query.iter().map(move |item| (*id, item)))
+    }
+
+    /// Iterates over the query results of every subworld
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (SubWorldId, <Q::Fetch as Fetch<'_>>::Item)>{
+        self.queries
+            .iter_mut()
+            .flat_map(|(id, query)| {
+                let id = *id;
+                query.iter_mut().map(move |item| (id, item))
+            })
+    }
+
+    fn index(&self, id: SubWorldId) -> Option<usize>{
+        self.queries.binary_search_by_key(&id, |(id, _)| *id).ok()
+    }
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParam for OtherQueries<'w, W, Q, F>
+where
+    F::Fetch: FilterFetch,{
+
+    type Fetch = OtherQueriesState<W, Q, F>;
+}
+
+pub struct OtherQueriesState<W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W> = ()>
+where
+    F::Fetch: FilterFetch,
+{
+    states: OtherQueryStates<SubWorldId, W, Q, F>,
+}
+
+// SAFE: the outer access of the query and a write of SubWorlds<W> are applied to SystemState, at
+// component and archetype level. If they conflict with any prior access, a panic will occur.
+unsafe impl<W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParamState for OtherQueriesState<W, Q, F>
+where
+    F::Fetch: FilterFetch,{
+
+    type Config = ();
+
+    fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
+        let worlds_id = world.initialize_resource::<SubWorlds<W>>();
+        let combined_access = system_state.component_access_set.combined_access();
+        if combined_access.has_read(worlds_id) || combined_access.has_write(worlds_id){
+            panic!(
+                "OtherQueries<{}, ...> in system {} conflicts with a Res<SubWorlds<{0}>> or ResMut<SubWorlds<{0}>> access. Allowing this would break Rust's mutability rules. Consider removing the duplicate access.",
+                std::any::type_name::<W>(), system_state.name);
+        }
+        Self {
+            states: OtherQueryStates::init(world, system_state, worlds_id),
+        }
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+        self.states.new_archetype(archetype, system_state);
+    }
+
+    fn default_config() {}
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParamFetch<'w> for OtherQueriesState<W, Q, F>
+where
+    F::Fetch: FilterFetch,{
+
+    type Item = OtherQueries<'w, W, Q, F>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'w mut Self,
+        _system_state: &'w SystemState,
+        world: &'w World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        let states = &mut state.states;
+        {
+            // SAFE: SubWorlds<W> is borrowed mutably by this param
+            let mut worlds = world.get_resource_unchecked_mut::<SubWorlds<W>>().expect("Couldn't find subworlds!");
+            states.retain(|id| worlds.get(id).map(|other| other.id()));
+            let new_ids = worlds
+                .ids()
+                .filter(|id| !states.contains(*id))
+                .collect::<Vec<_>>();
+            // NOTE: SubWorlds<W> is only flagged as changed when a subworld shows up
+            for id in new_ids {
+                states.insert(id, worlds.get_mut(id).unwrap());
+            }
+        }
+        let worlds = world.get_resource::<SubWorlds<W>>().unwrap();
+        OtherQueries {
+            queries: states.get_other_queries(|id| worlds.get(id)),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,152 @@
+use crate::other_query::OtherQuery;
+use crate::other_query_state::OtherQueryStates;
+use crate::other_world_query::init_component_id;
+use crate::other_world_query::OtherWorldQuery;
+use bevy::ecs::archetype::Archetype;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::Fetch;
+use bevy::ecs::query::FilterFetch;
+use bevy::ecs::query::QueryState;
+use bevy::ecs::query::ReadOnlyFetch;
+use bevy::ecs::query::With;
+use bevy::ecs::query::WorldQuery;
+use bevy::ecs::system::SystemParam;
+use bevy::ecs::system::SystemParamFetch;
+use bevy::ecs::system::SystemParamState;
+use bevy::ecs::system::SystemState;
+use bevy::ecs::world::World;
+use core::ops::DerefMut;
+
+/// Runs the same query inside the subworld `W` of every entity that has `W` as a component.
+///
+/// The query is set up for entities that gained a `W` since the last run when the system runs,
+/// which flags their `W` as changed. Since that takes mutable access to the `W` components, no
+/// other system using them runs alongside.
+pub struct PerEntityOtherQuery<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static = ()>
+where
+    F::Fetch: FilterFetch,
+{
+    // NOTE: sorted by entity, so iteration order doesn't depend on the HashMap
+    queries: Vec<(Entity, OtherQuery<'w, W, Q, F>)>,
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> PerEntityOtherQuery<'w, W, Q, F>
+where
+    F::Fetch: FilterFetch,
+{
+    /// The query over the subworld of `entity`.
+    pub fn get(&self, entity: Entity) -> Option<&OtherQuery<'w, W, Q, F>>{
+        self.index(entity).map(|index| &self.queries[index].1)
+    }
+
+    /// The query over the subworld of `entity`.
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut OtherQuery<'w, W, Q, F>>{
+        let index = self.index(entity)?;
+        Some(&mut self.queries[index].1)
+    }
+
+    /// The entities whose subworlds this queries.
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_{
+        self.queries.iter().map(|(entity, _)| *entity)
+    }
+
+    /// Iterates over the query results of every subworld. This can only be called for read-only queries
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, <Q::Fetch as Fetch<'_>>::Item)>
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        self.queries
+            .iter()
+            .flat_map(|(entity, query)| query.iter().map(move |item| (*entity, item)))
+    }
+
+    /// Iterates over the query results of every subworld
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, <Q::Fetch as Fetch<'_>>::Item)>{
+        self.queries
+            .iter_mut()
+            .flat_map(|(entity, query)| {
+                let entity = *entity;
+                query.iter_mut().map(move |item| (entity, item))
+            })
+    }
+
+    fn index(&self, entity: Entity) -> Option<usize>{
+        self.queries.binary_search_by_key(&entity, |(entity, _)| *entity).ok()
+    }
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParam for PerEntityOtherQuery<'w, W, Q, F>
+where
+    F::Fetch: FilterFetch,{
+
+    type Fetch = PerEntityOtherQueryState<W, Q, F>;
+}
+
+pub struct PerEntityOtherQueryState<W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W>, F: WorldQuery + OtherWorldQuery<W> = ()>
+where
+    F::Fetch: FilterFetch,
+{
+    entities: QueryState<Entity, With<W>>,
+    states: OtherQueryStates<Entity, W, Q, F>,
+}
+
+// SAFE: the outer access of the query and a write of the W components are applied to SystemState,
+// at component and archetype level. If any of it conflicts with prior access, a panic will occur.
+unsafe impl<W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParamState for PerEntityOtherQueryState<W, Q, F>
+where
+    F::Fetch: FilterFetch,{
+
+    type Config = ();
+
+    fn init(world: &mut World, system_state: &mut SystemState, _config: Self::Config) -> Self {
+        let component_id = init_component_id::<W>(world);
+        Self {
+            entities: world.query_filtered::<Entity, With<W>>(),
+            states: OtherQueryStates::init(world, system_state, component_id),
+        }
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype, system_state: &mut SystemState) {
+        self.states.new_archetype(archetype, system_state);
+    }
+
+    fn default_config() {}
+}
+
+impl<'w, W: DerefMut<Target = World> + Component, Q: WorldQuery + OtherWorldQuery<W> + 'static, F: WorldQuery + OtherWorldQuery<W> + 'static> SystemParamFetch<'w> for PerEntityOtherQueryState<W, Q, F>
+where
+    F::Fetch: FilterFetch,{
+
+    type Item = PerEntityOtherQuery<'w, W, Q, F>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'w mut Self,
+        _system_state: &'w SystemState,
+        world: &'w World,
+        change_tick: u32,
+    ) -> Self::Item {
+        let states = &mut state.states;
+        states.retain(|entity| world.get::<W>(entity).map(|other| other.id()));
+        let new_entities = state
+            .entities
+            .iter(world)
+            .filter(|entity| !states.contains(*entity))
+            .collect::<Vec<_>>();
+        // NOTE: setting up the query takes mutable access to the subworld, so only the W of an
+        // entity the query hasn't seen before is flagged as changed
+        for entity in new_entities {
+            // SAFE: the W components are borrowed mutably by this param
+            let mut other = world
+                .get_entity(entity)
+                .unwrap()
+                .get_unchecked_mut::<W>(world.last_change_tick(), change_tick)
+                .unwrap();
+            states.insert(entity, &mut other);
+        }
+        PerEntityOtherQuery {
+            queries: states.get_other_queries(|entity| world.get::<W>(entity)),
+        }
+    }
+}
\ No newline at end of file